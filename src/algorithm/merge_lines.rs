@@ -1,7 +1,8 @@
 use geo::{Contains, Coord, CoordsIter, GeoFloat, Intersects, LineString};
 use itertools::Itertools;
 use rayon::iter::{ParallelBridge, ParallelIterator};
-use rstar::RTreeObject;
+use rstar::{RTree, RTreeObject, AABB};
+use std::collections::HashMap;
 
 // Used to merge two linestrings that intersect on either endpoint.
 fn merge_two<T: GeoFloat>(a: &LineString<T>, b: &LineString<T>) -> Option<LineString<T>> {
@@ -209,6 +210,156 @@ pub fn merge_linestrings<T: GeoFloat + Send + Sync>(
     }
 }
 
+/// A linestring endpoint, tagged with the line it belongs to and whether it's the start or the
+/// end, so [`snap_endpoints`] can write a snapped coordinate back to the right place once grouped.
+#[derive(Clone, Copy)]
+struct Endpoint<T: GeoFloat> {
+    coord: Coord<T>,
+    line: usize,
+    is_start: bool,
+}
+
+impl<T: GeoFloat> RTreeObject for Endpoint<T> {
+    type Envelope = AABB<[T; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point([self.coord.x, self.coord.y])
+    }
+}
+
+/// Plain union-find (disjoint-set) over a fixed number of elements, identified by index.
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        UnionFind {
+            parent: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, node: usize) -> usize {
+        if self.parent[node] != node {
+            self.parent[node] = self.find(self.parent[node]);
+        }
+        self.parent[node]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent[root_a] = root_b;
+        }
+    }
+}
+
+/// Collapses near-coincident linestring endpoints before [`merge_linestrings`] runs, since both
+/// it and [`merge_two`] rely on exact `Coord::intersects` and never merge endpoints that are only
+/// a few floating-point ULPs apart — a constant problem with digitized shapefiles.
+///
+/// Every line's start/end coordinate is bulk-loaded into an [`RTree`], then for each endpoint a
+/// `tolerance`-wide box is queried and every mutually-close pair is unioned together, so groups
+/// form transitively (A close to B, B close to C snaps all three together even if A and C aren't
+/// directly within `tolerance`). Each group is then snapped to the centroid of its members, and
+/// every endpoint in the group is rewritten to that centroid.
+///
+/// Two-point lines whose start and end land in the same group after snapping are dropped, since a
+/// zero-length line isn't a meaningful segment to hand to [`merge_two`]. A group whose endpoints
+/// already share one coordinate keeps that coordinate exactly, so running `snap_endpoints` again
+/// on its own output leaves it unchanged, except where a group's centroid has landed within
+/// `tolerance` of another group's endpoint: those two groups are then snapped together.
+pub fn snap_endpoints<T: GeoFloat>(lines: Vec<LineString<T>>, tolerance: T) -> Vec<LineString<T>> {
+    if tolerance <= T::zero() {
+        return lines;
+    }
+
+    let endpoints: Vec<Endpoint<T>> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(line, linestring)| {
+            let last = linestring.0.len() - 1;
+            [
+                Endpoint {
+                    coord: linestring.0[0],
+                    line,
+                    is_start: true,
+                },
+                Endpoint {
+                    coord: linestring.0[last],
+                    line,
+                    is_start: false,
+                },
+            ]
+        })
+        .collect();
+
+    let tree = RTree::bulk_load(endpoints.clone());
+    let mut union_find = UnionFind::new(endpoints.len());
+    let tolerance_2 = tolerance * tolerance;
+    for (id, endpoint) in endpoints.iter().enumerate() {
+        let envelope = AABB::from_corners(
+            [endpoint.coord.x - tolerance, endpoint.coord.y - tolerance],
+            [endpoint.coord.x + tolerance, endpoint.coord.y + tolerance],
+        );
+        for neighbour in tree.locate_in_envelope(&envelope) {
+            let dx = neighbour.coord.x - endpoint.coord.x;
+            let dy = neighbour.coord.y - endpoint.coord.y;
+            if dx * dx + dy * dy <= tolerance_2 {
+                let neighbour_id = neighbour.line * 2 + if neighbour.is_start { 0 } else { 1 };
+                union_find.union(id, neighbour_id);
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<Coord<T>>> = HashMap::new();
+    for (id, endpoint) in endpoints.iter().enumerate() {
+        let root = union_find.find(id);
+        groups.entry(root).or_default().push(endpoint.coord);
+    }
+    let centroids: HashMap<usize, Coord<T>> = groups
+        .into_iter()
+        .map(|(root, coords)| {
+            // Summing and dividing can round an already-coincident group off by an ULP.
+            if coords.iter().all(|coord| *coord == coords[0]) {
+                return (root, coords[0]);
+            }
+            let count = T::from(coords.len()).expect("Endpoint count must fit in T.");
+            let sum = coords.iter().fold(Coord { x: T::zero(), y: T::zero() }, |acc, coord| {
+                Coord {
+                    x: acc.x + coord.x,
+                    y: acc.y + coord.y,
+                }
+            });
+            (
+                root,
+                Coord {
+                    x: sum.x / count,
+                    y: sum.y / count,
+                },
+            )
+        })
+        .collect();
+
+    lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(line, mut linestring)| {
+            let last = linestring.0.len() - 1;
+            let start_id = line * 2;
+            let end_id = line * 2 + 1;
+            let start = centroids[&union_find.find(start_id)];
+            let end = centroids[&union_find.find(end_id)];
+            if last == 0 || (last == 1 && start == end) {
+                return None;
+            }
+            linestring.0[0] = start;
+            linestring.0[last] = end;
+            Some(linestring)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -321,4 +472,48 @@ mod tests {
     //     assert!(computed.len() != 0);
     //     geometries_to_file(computed, "./assets/lines_smaller_merged.shp", None, None);
     // }
+
+    #[test]
+    fn snap_endpoints_merges_near_coincident_vertices() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 2.0000001, y: 2.0000001), (x: 3., y: 3.)],
+        ];
+        let snapped = snap_endpoints(input, 0.001);
+        assert_eq!(snapped[0].0[1], snapped[1].0[0]);
+        let centroid = (2. + 2.0000001) / 2.;
+        let output = merge_linestrings(snapped);
+        assert!(output.contains(&line_string![
+            (x: 1., y: 1.),
+            (x: centroid, y: centroid),
+            (x: 3., y: 3.)
+        ]));
+    }
+
+    #[test]
+    fn snap_endpoints_leaves_distant_lines_untouched() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 3., y: 3.), (x: 4., y: 4.)],
+        ];
+        let output = input.clone();
+        assert_eq!(snap_endpoints(input, 0.001), output);
+    }
+
+    #[test]
+    fn snap_endpoints_drops_degenerate_segments() {
+        let input = vec![line_string![(x: 1., y: 1.), (x: 1.0000001, y: 1.0000001)]];
+        assert!(snap_endpoints(input, 0.001).is_empty());
+    }
+
+    #[test]
+    fn snap_endpoints_is_idempotent() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 2.0000001, y: 2.0000001), (x: 3., y: 3.)],
+        ];
+        let once = snap_endpoints(input, 0.001);
+        let twice = snap_endpoints(once.clone(), 0.001);
+        assert_eq!(once, twice);
+    }
 }
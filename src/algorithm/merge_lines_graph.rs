@@ -0,0 +1,296 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use geo::{sweep::SweepPoint, Coord, CoordsIter, GeoFloat, Intersects, LineString};
+
+// Changes the startpoint/endpoint of a closed linestring.
+fn rotate_start_point<T: GeoFloat>(linestring: &LineString<T>, at: Coord<T>) -> LineString<T> {
+    let coords = linestring.coords_iter();
+    let count = coords.len();
+    let mut repeated = std::iter::repeat(coords).flatten();
+    loop {
+        if let Some(coord) = repeated.next() {
+            if coord.intersects(&at) {
+                return LineString::from_iter(std::iter::once(coord).chain(repeated.take(count)));
+            }
+        }
+    }
+}
+
+/// Merges `partner` onto `chain` at the specific coordinate `end` (one of `chain`'s two
+/// endpoints, per `at_start`). Unlike a generic "do any of these four endpoint pairs match"
+/// merge, this only ever splices at `end`, so it can't be fooled into merging at a different,
+/// coincidentally-equal coordinate elsewhere on `partner` (which matters once `partner`'s other
+/// endpoint happens to equal `chain`'s other end too, e.g. right before a ring closes).
+fn extend_at<T: GeoFloat>(
+    chain: &LineString<T>,
+    at_start: bool,
+    end: Coord<T>,
+    partner: &LineString<T>,
+) -> LineString<T> {
+    let partner_starts_at_end = partner.0[0].intersects(&end);
+    if at_start {
+        if partner_starts_at_end {
+            LineString::from_iter(partner.coords_iter().rev().chain(chain.coords_iter().skip(1)))
+        } else {
+            LineString::from_iter(partner.coords_iter().chain(chain.coords_iter().skip(1)))
+        }
+    } else if partner_starts_at_end {
+        LineString::from_iter(chain.coords_iter().chain(partner.coords_iter().skip(1)))
+    } else {
+        LineString::from_iter(chain.coords_iter().chain(partner.coords_iter().rev().skip(1)))
+    }
+}
+
+/// Builds, for every distinct coordinate, the indices of the input lines with an endpoint there,
+/// and the set of coordinates that are an interior vertex of some input line. A coordinate is a
+/// mergeable degree-2 node only when exactly two lines have an endpoint there and no line passes
+/// through it as an interior vertex; anything else (three or more endpoints, or a through-line) is
+/// a junction where merging must stop.
+fn endpoint_index<T: GeoFloat>(
+    lines: &[LineString<T>],
+) -> (BTreeMap<SweepPoint<T>, Vec<usize>>, BTreeSet<SweepPoint<T>>) {
+    let mut endpoints: BTreeMap<SweepPoint<T>, Vec<usize>> = BTreeMap::new();
+    let mut interior: BTreeSet<SweepPoint<T>> = BTreeSet::new();
+    for (index, line) in lines.iter().enumerate() {
+        let last = line.coords_count() - 1;
+        endpoints.entry(line.0[0].into()).or_default().push(index);
+        endpoints.entry(line.0[last].into()).or_default().push(index);
+        for coord in &line.0[1..last] {
+            interior.insert((*coord).into());
+        }
+    }
+    (endpoints, interior)
+}
+
+/// Extends `chain` outward from either end, merging in whichever line is the sole unvisited
+/// occupant of a mergeable degree-2 node at that end, until both ends hit a junction (or a
+/// dangling end with no partner) or the chain closes into a ring.
+fn extend_chain<T: GeoFloat>(
+    mut chain: LineString<T>,
+    visited: &mut [bool],
+    lines: &[LineString<T>],
+    endpoints: &BTreeMap<SweepPoint<T>, Vec<usize>>,
+    interior: &BTreeSet<SweepPoint<T>>,
+) -> LineString<T> {
+    loop {
+        if chain.is_closed() {
+            break;
+        }
+        let mut merged = false;
+        for at_start in [true, false] {
+            let end = if at_start {
+                chain.0[0]
+            } else {
+                chain.0[chain.coords_count() - 1]
+            };
+            let key: SweepPoint<T> = end.into();
+            if interior.contains(&key) {
+                continue;
+            }
+            let touching = match endpoints.get(&key) {
+                Some(touching) if touching.len() == 2 => touching,
+                _ => continue,
+            };
+            let mut unvisited = touching.iter().copied().filter(|&index| !visited[index]);
+            let partner = match (unvisited.next(), unvisited.next()) {
+                (Some(partner), None) => partner,
+                _ => continue,
+            };
+            chain = extend_at(&chain, at_start, end, &lines[partner]);
+            visited[partner] = true;
+            merged = true;
+            break;
+        }
+        if !merged {
+            break;
+        }
+    }
+    chain
+}
+
+/// Rotates a freshly closed ring's start point onto any other, still-unmerged line's endpoint
+/// that touches the ring — the same adjustment [`crate::algorithm::merge_linestrings`] makes.
+/// Without it, a line attaching to the ring anywhere other than its current start/end coordinate
+/// could never be found to merge with it later, since [`extend_at`] only ever looks at a
+/// linestring's first and last coordinate.
+fn realign_closed_ring<T: GeoFloat>(
+    ring: LineString<T>,
+    lines: &[LineString<T>],
+    visited: &[bool],
+) -> LineString<T> {
+    let start = ring.0[0];
+    let attachment = lines.iter().enumerate().find_map(|(index, other)| {
+        if visited[index] {
+            return None;
+        }
+        let last = other.coords_count() - 1;
+        ring.coords_iter().find_map(|coord| {
+            if coord == start {
+                None
+            } else if other.0[0].intersects(&coord) || other.0[last].intersects(&coord) {
+                Some(coord)
+            } else {
+                None
+            }
+        })
+    });
+    match attachment {
+        Some(coord) => rotate_start_point(&ring, coord),
+        None => ring,
+    }
+}
+
+/// A single-pass rewrite of [`crate::algorithm::merge_linestrings`]: rather than re-scanning every
+/// remaining linestring against every other one, once per pass, until the result stabilizes, this
+/// builds the endpoint connectivity graph exactly once (see [`endpoint_index`]) and walks each
+/// maximal degree-2 chain out to its junctions in a single traversal.
+/// [`crate::algorithm::merge_linestring_optimized`] is a partial RTree workaround for the same
+/// quadratic cost; this replaces the convergence loop entirely instead of just speeding up each
+/// pass through it.
+pub fn merge_linestrings_graph<T: GeoFloat>(lines: Vec<LineString<T>>) -> Vec<LineString<T>> {
+    let (endpoints, interior) = endpoint_index(&lines);
+    let mut visited = vec![false; lines.len()];
+    let mut output = Vec::new();
+
+    for index in 0..lines.len() {
+        if visited[index] {
+            continue;
+        }
+        visited[index] = true;
+        let mut chain = extend_chain(
+            lines[index].clone(),
+            &mut visited,
+            &lines,
+            &endpoints,
+            &interior,
+        );
+        if chain.is_closed() {
+            chain = realign_closed_ring(chain, &lines, &visited);
+        }
+        output.push(chain);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::line_string;
+
+    #[test]
+    fn test_one() {
+        let input = vec![line_string![(x: 1., y: 1.), (x: 2., y: 2.)]];
+        let output = input.clone();
+        assert_eq!(merge_linestrings_graph(input), output);
+    }
+
+    #[test]
+    fn touches_two() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 2., y: 2.), (x: 3., y: 3.)],
+        ];
+        let output = merge_linestrings_graph(input);
+        assert_eq!(
+            output,
+            vec![line_string![(x: 1., y: 1.), (x: 2., y: 2.), (x: 3., y: 3.)]]
+        );
+    }
+
+    #[test]
+    fn touches_three() {
+        let input = vec![
+            line_string![(x: -21.95156, y: 64.1446), (x: -21.951, y: 64.14479)],
+            line_string![(x: -21.951, y: 64.14479), (x: -21.95044, y: 64.14527)],
+            line_string![(x: -21.95044, y: 64.14527), (x: -21.951445, y: 64.145508)],
+        ];
+        let output = vec![line_string![
+            (x: -21.95156, y: 64.1446),
+            (x: -21.951, y: 64.14479),
+            (x: -21.95044, y: 64.14527),
+            (x: -21.951445, y: 64.145508),
+        ]];
+        assert_eq!(merge_linestrings_graph(input), output);
+    }
+
+    #[test]
+    fn disjoint_two() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 3., y: 3.), (x: 4., y: 4.)],
+        ];
+        let output = input.clone();
+        assert_eq!(merge_linestrings_graph(input), output);
+    }
+
+    #[test]
+    fn disjoin_with_touch() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 2., y: 2.), (x: 3., y: 3.)],
+            line_string![(x: 3., y: 3.), (x: 4., y: 4.)],
+            line_string![(x: 7., y: 7.), (x: 8., y: 8.)],
+        ];
+        let output = merge_linestrings_graph(input);
+        assert!(output.contains(&line_string![
+            (x: 1., y: 1.),
+            (x: 2., y: 2.),
+            (x: 3., y: 3.),
+            (x: 4., y: 4.)
+        ]));
+        assert!(output.contains(&line_string![(x: 7., y: 7.), (x: 8., y: 8.)]));
+    }
+
+    #[test]
+    fn intersect_three() {
+        let input: Vec<LineString> = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 2., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 1., y: 2.), (x: 2., y: 2.)],
+        ];
+        let output = merge_linestrings_graph(input.clone());
+        assert!(output.contains(&input[0]));
+        assert!(output.contains(&input[1]));
+        assert!(output.contains(&input[2]));
+    }
+
+    #[test]
+    fn intersect_and_disjoint() {
+        let input: Vec<LineString> = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.)], // intersected
+            line_string![(x: 1., y: 2.), (x: 2., y: 2.)], // intersected
+            line_string![(x: 1., y: 3.), (x: 2., y: 2.)], // intersected
+            line_string![(x: 3., y: 3.), (x: 4., y: 4.)], // disjoint
+        ];
+        let output = merge_linestrings_graph(input.clone());
+        assert!(output.contains(&input[0]));
+        assert!(output.contains(&input[1]));
+        assert!(output.contains(&input[2]));
+        assert!(output.contains(&input[3]));
+    }
+
+    #[test]
+    fn closes_into_ring() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 1., y: 1.)],
+            line_string![(x: 1., y: 1.), (x: 0., y: 0.)],
+        ];
+        let output = merge_linestrings_graph(input);
+        assert_eq!(output.len(), 1);
+        assert!(output[0].is_closed());
+    }
+
+    #[test]
+    fn junction_blocks_merge_on_both_sides() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 2., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 1., y: 1.)],
+        ];
+        let output = merge_linestrings_graph(input.clone());
+        assert!(output.contains(&input[0]));
+        assert!(output.contains(&input[1]));
+        assert!(output.contains(&input[2]));
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeSet;
+
+use crate::util::{explode_linestrings, intersections};
+use geo::{sweep::SweepPoint, Coord, GeoFloat, Intersects, Line, LineString, Point};
+
+/// Returns the parameter `t` in `(0, 1)` at which `point` falls on the open segment from `start`
+/// to `end`, or `None` if `point` doesn't lie on the segment or coincides with one of its
+/// endpoints (an endpoint coincidence is already a natural break in the coordinate sequence, not
+/// a split).
+fn segment_param<T: GeoFloat>(start: Coord<T>, end: Coord<T>, point: Coord<T>) -> Option<T> {
+    if point == start || point == end {
+        return None;
+    }
+    if !Line::new(start, end).intersects(&Point::from(point)) {
+        return None;
+    }
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let t = if dx.abs() > dy.abs() {
+        (point.x - start.x) / dx
+    } else {
+        (point.y - start.y) / dy
+    };
+    Some(t)
+}
+
+/// Splits `linestring` at every coordinate in `split_coords` that falls in its interior (on one
+/// of its segments, excluding coordinates that already coincide with one of its own vertices).
+fn split_linestring<T: GeoFloat>(
+    linestring: &LineString<T>,
+    split_coords: &[Coord<T>],
+) -> Vec<LineString<T>> {
+    let mut pieces = Vec::new();
+    let mut current = vec![linestring.0[0]];
+
+    for window in linestring.0.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        let mut splits: Vec<(T, Coord<T>)> = split_coords
+            .iter()
+            .filter_map(|&coord| segment_param(start, end, coord).map(|t| (t, coord)))
+            .collect();
+        splits.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Encountered a NaN coordinate."));
+
+        for (_, coord) in splits {
+            current.push(coord);
+            pieces.push(LineString::new(std::mem::replace(&mut current, vec![coord])));
+        }
+        current.push(end);
+    }
+    pieces.push(LineString::new(current));
+    pieces
+}
+
+/// Nodes a linestring network by breaking every input line at every point where it crosses or
+/// touches another one (the "break lines" step of GRASS `v.clean`), so the result is a planar
+/// graph where segments only ever meet at shared endpoints. This is the natural inverse of
+/// [`crate::algorithm::merge_linestrings`], which joins lines back together at shared endpoints.
+///
+/// Reuses [`intersections`] (the same sweep [`crate::rule::MustNotIntersect`] is built on) to
+/// find every proper (X-crossing, not at any vertex) and improper (touching an existing vertex)
+/// intersection coordinate across the whole input, then splits each linestring at whichever of
+/// those coordinates fall on one of its segments. A split landing exactly on an existing vertex
+/// is skipped rather than inserted again, since the vertex is already a break in the coordinate
+/// sequence and re-inserting it would produce a zero-length segment; a proper X-crossing, which by
+/// definition isn't at a vertex of either line, is inserted into both crossing linestrings.
+pub fn node_linestrings<T: GeoFloat + Send + Sync>(lines: Vec<LineString<T>>) -> Vec<LineString<T>> {
+    let segments = explode_linestrings(&lines);
+    let (_, (proper, improper)) = intersections::<T, SweepPoint<T>, SweepPoint<T>>(segments);
+    let split_points: BTreeSet<SweepPoint<T>> = proper.into_iter().chain(improper).collect();
+    let split_coords: Vec<Coord<T>> = split_points
+        .into_iter()
+        .map(|point| Coord { x: point.x, y: point.y })
+        .collect();
+
+    lines
+        .iter()
+        .flat_map(|linestring| split_linestring(linestring, &split_coords))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::line_string;
+
+    #[test]
+    fn splits_at_x_crossing() {
+        let input = vec![
+            line_string![(x: 0., y: 1.), (x: 2., y: 1.)],
+            line_string![(x: 1., y: 0.), (x: 1., y: 2.)],
+        ];
+        let output = node_linestrings(input);
+        assert_eq!(output.len(), 4);
+        assert!(output.contains(&line_string![(x: 0., y: 1.), (x: 1., y: 1.)]));
+        assert!(output.contains(&line_string![(x: 1., y: 1.), (x: 2., y: 1.)]));
+        assert!(output.contains(&line_string![(x: 1., y: 0.), (x: 1., y: 1.)]));
+        assert!(output.contains(&line_string![(x: 1., y: 1.), (x: 1., y: 2.)]));
+    }
+
+    #[test]
+    fn no_crossing_is_unaffected() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            line_string![(x: 5., y: 5.), (x: 6., y: 6.)],
+        ];
+        let output = node_linestrings(input.clone());
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn touching_at_existing_vertex_is_not_re_split() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 2.)],
+            line_string![(x: 1., y: 1.), (x: 2., y: 0.)],
+        ];
+        let output = node_linestrings(input.clone());
+        assert_eq!(output, input);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::util::{explode_linestrings, intersections, linestring_endpoints};
+use geo::{sweep::SweepPoint, EuclideanLength, GeoFloat, LineString};
+
+/// Mirrors GRASS `v.clean tool=rmdangle`: deletes dangling lines — a line with at least one
+/// endpoint not shared by any other line — whose total length is below `max_length`, leaving
+/// longer dangles (real features, not digitizing slivers) intact. `max_length` of `None` removes
+/// every dangle regardless of length.
+///
+/// Reuses the same [`linestring_endpoints`] + [`intersections`] technique
+/// [`crate::rule::MustNotHaveDangles`] reports with: an endpoint that isn't an `improper`
+/// intersection point isn't shared with any other line, so it's free. Removing one dangle can
+/// expose a new one (the line it used to keep alive at a junction node may now dangle too), so
+/// this iterates to a fixed point, the same convergence style
+/// [`crate::algorithm::merge_linestrings`] uses.
+pub fn remove_dangles<T: GeoFloat + Send + Sync>(
+    mut lines: Vec<LineString<T>>,
+    max_length: Option<T>,
+) -> Vec<LineString<T>> {
+    loop {
+        let (_, (_, improper)) =
+            intersections::<T, SweepPoint<T>, SweepPoint<T>>(explode_linestrings(&lines));
+        let endpoints = linestring_endpoints(&lines);
+
+        let before = lines.len();
+        lines = lines
+            .into_iter()
+            .enumerate()
+            .filter(|(index, linestring)| {
+                let start = endpoints[index * 2];
+                let end = endpoints[index * 2 + 1];
+                let is_dangle = !improper.contains(&start) || !improper.contains(&end);
+                if !is_dangle {
+                    return true;
+                }
+                match max_length {
+                    Some(max_length) => linestring.euclidean_length() >= max_length,
+                    None => false,
+                }
+            })
+            .map(|(_, linestring)| linestring)
+            .collect();
+        if lines.len() == before {
+            break;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::line_string;
+
+    #[test]
+    fn short_dangle_is_removed() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 2., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 1., y: 0.1)],
+        ];
+        let output = remove_dangles(input.clone(), Some(1.0));
+        assert_eq!(
+            output,
+            vec![input[0].clone(), input[1].clone()]
+        );
+    }
+
+    #[test]
+    fn long_dangle_is_kept() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 2., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 1., y: 5.)],
+        ];
+        let output = remove_dangles(input.clone(), Some(1.0));
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn no_threshold_removes_every_dangle() {
+        // Every one of these segments has a free endpoint, so with no threshold all of them are
+        // removed, even though all three share the junction at (1, 0).
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 2., y: 0.)],
+            line_string![(x: 1., y: 0.), (x: 1., y: 5.)],
+        ];
+        let output = remove_dangles(input, None);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn removing_one_dangle_exposes_another() {
+        // line_b only dangles once line_a, the short spur hanging off its free end, is removed.
+        let line_a = line_string![(x: 0.0, y: 0.), (x: 0.5, y: 0.)];
+        let line_b = line_string![(x: 0.5, y: 0.), (x: 1.0, y: 0.)];
+        let line_c = line_string![(x: 1.0, y: 0.), (x: 2.0, y: 0.)];
+        let output = remove_dangles(vec![line_a, line_b, line_c.clone()], Some(0.6));
+        assert_eq!(output, vec![line_c]);
+    }
+}
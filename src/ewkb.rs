@@ -0,0 +1,151 @@
+//! Minimal EWKB (Extended Well-Known Binary) writer used to push topology errors
+//! straight into a PostGIS table, without going through a GDAL driver.
+//!
+//! Layout follows the PostGIS EWKB extension of OGC WKB: a byte-order byte, a
+//! little/big-endian `u32` geometry type with the `0x20000000` bit set when an SRID is
+//! present, an optional `u32` SRID, and then the plain WKB body for that type.
+use geo::{CoordsIter, GeoFloat, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+
+const SRID_FLAG: u32 = 0x2000_0000;
+
+const WKB_POINT: u32 = 1;
+const WKB_LINESTRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOINT: u32 = 4;
+const WKB_MULTILINESTRING: u32 = 5;
+const WKB_MULTIPOLYGON: u32 = 6;
+
+/// Implemented by every geometry type that [`crate::TopologyError`] can hold, so each
+/// variant can be serialized to EWKB without a match statement at the call site.
+pub trait ToEwkb<T: GeoFloat> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8>;
+}
+
+fn write_header(buffer: &mut Vec<u8>, geometry_type: u32, srid: Option<u32>) {
+    // Byte order: 1 == little-endian, matching the coordinate writes below.
+    buffer.push(1);
+    let mut tagged_type = geometry_type;
+    if srid.is_some() {
+        tagged_type |= SRID_FLAG;
+    }
+    buffer.extend_from_slice(&tagged_type.to_le_bytes());
+    if let Some(srid) = srid {
+        buffer.extend_from_slice(&srid.to_le_bytes());
+    }
+}
+
+fn write_coord<T: GeoFloat>(buffer: &mut Vec<u8>, x: T, y: T) {
+    let x = x.to_f64().expect("Failed to convert coordinate x to f64.");
+    let y = y.to_f64().expect("Failed to convert coordinate y to f64.");
+    buffer.extend_from_slice(&x.to_le_bytes());
+    buffer.extend_from_slice(&y.to_le_bytes());
+}
+
+fn write_linestring_body<T: GeoFloat>(buffer: &mut Vec<u8>, linestring: &LineString<T>) {
+    buffer.extend_from_slice(&(linestring.coords_count() as u32).to_le_bytes());
+    for coord in linestring.coords() {
+        write_coord(buffer, coord.x, coord.y);
+    }
+}
+
+fn write_polygon_body<T: GeoFloat>(buffer: &mut Vec<u8>, polygon: &Polygon<T>) {
+    let ring_count = 1 + polygon.interiors().len();
+    buffer.extend_from_slice(&(ring_count as u32).to_le_bytes());
+    write_linestring_body(buffer, polygon.exterior());
+    for interior in polygon.interiors() {
+        write_linestring_body(buffer, interior);
+    }
+}
+
+impl<T: GeoFloat> ToEwkb<T> for Point<T> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_header(&mut buffer, WKB_POINT, srid);
+        write_coord(&mut buffer, self.x(), self.y());
+        buffer
+    }
+}
+
+impl<T: GeoFloat> ToEwkb<T> for LineString<T> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_header(&mut buffer, WKB_LINESTRING, srid);
+        write_linestring_body(&mut buffer, self);
+        buffer
+    }
+}
+
+impl<T: GeoFloat> ToEwkb<T> for Polygon<T> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_header(&mut buffer, WKB_POLYGON, srid);
+        write_polygon_body(&mut buffer, self);
+        buffer
+    }
+}
+
+impl<T: GeoFloat> ToEwkb<T> for MultiPoint<T> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_header(&mut buffer, WKB_MULTIPOINT, srid);
+        buffer.extend_from_slice(&(self.0.len() as u32).to_le_bytes());
+        for point in &self.0 {
+            // Parts of a multi-geometry are plain WKB, never tagged with their own SRID.
+            buffer.extend_from_slice(&point.to_ewkb(None));
+        }
+        buffer
+    }
+}
+
+impl<T: GeoFloat> ToEwkb<T> for MultiLineString<T> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_header(&mut buffer, WKB_MULTILINESTRING, srid);
+        buffer.extend_from_slice(&(self.0.len() as u32).to_le_bytes());
+        for linestring in &self.0 {
+            buffer.extend_from_slice(&linestring.to_ewkb(None));
+        }
+        buffer
+    }
+}
+
+impl<T: GeoFloat> ToEwkb<T> for MultiPolygon<T> {
+    fn to_ewkb(&self, srid: Option<u32>) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_header(&mut buffer, WKB_MULTIPOLYGON, srid);
+        buffer.extend_from_slice(&(self.0.len() as u32).to_le_bytes());
+        for polygon in &self.0 {
+            buffer.extend_from_slice(&polygon.to_ewkb(None));
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::point;
+
+    #[test]
+    fn point_header_carries_srid_flag() {
+        let bytes = point! { x: 1.0, y: 2.0 }.to_ewkb(Some(4326));
+        assert_eq!(bytes[0], 1, "byte order should be little-endian");
+        let geometry_type = u32::from_le_bytes(bytes[1..5].try_into().unwrap());
+        assert_eq!(geometry_type & SRID_FLAG, SRID_FLAG);
+        assert_eq!(geometry_type & !SRID_FLAG, WKB_POINT);
+        let srid = u32::from_le_bytes(bytes[5..9].try_into().unwrap());
+        assert_eq!(srid, 4326);
+        let x = f64::from_le_bytes(bytes[9..17].try_into().unwrap());
+        let y = f64::from_le_bytes(bytes[17..25].try_into().unwrap());
+        assert_eq!((x, y), (1.0, 2.0));
+    }
+
+    #[test]
+    fn point_without_srid_omits_the_flag_and_field() {
+        let bytes = point! { x: 1.0, y: 2.0 }.to_ewkb(None);
+        let geometry_type = u32::from_le_bytes(bytes[1..5].try_into().unwrap());
+        assert_eq!(geometry_type, WKB_POINT);
+        // header (1 + 4) + 2 f64 coordinates, no SRID field in between.
+        assert_eq!(bytes.len(), 5 + 16);
+    }
+}
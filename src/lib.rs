@@ -1,4 +1,4 @@
-use crate::util::{create_dataset, open_dataset, GdalDrivers};
+use crate::util::{create_dataset, geometries_to_wkt, open_dataset, GdalDrivers, OutputFormat};
 use anyhow::Context;
 use gdal::{
     errors::GdalError,
@@ -7,90 +7,480 @@ use gdal::{
     Dataset, LayerOptions, Metadata,
 };
 use geo::{
-    GeoFloat, Geometry, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+    BooleanOps, Coord, GeoFloat, Geometry, Intersects, Line, LineString, MultiLineString,
+    MultiPoint, MultiPolygon, Point, Polygon, Rect,
 };
 use geozero::{gdal::process_geom, geo_types::GeoWriter};
-use std::{fmt::Display, path::PathBuf};
+use std::{fmt::Display, path::PathBuf, str::FromStr};
 
 pub mod algorithm;
+pub mod ewkb;
 pub mod prelude;
 pub mod rule;
 pub mod util;
 
-pub struct VectorDataset(Dataset);
+/// Raised by [`VectorDataset::to_geo_as`] when a coordinate read from the dataset cannot be
+/// represented in the target [`GeoFloat`] (e.g. an `f64` tile extent that overflows `f32`).
+#[derive(Debug)]
+pub struct CastError;
+
+impl Display for CastError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to cast a coordinate to the target precision")
+    }
+}
+
+impl std::error::Error for CastError {}
+
+fn cast_coord<T: GeoFloat>(coord: geo::Coord<f64>) -> Result<geo::Coord<T>, CastError> {
+    Ok(geo::Coord {
+        x: T::from(coord.x).ok_or(CastError)?,
+        y: T::from(coord.y).ok_or(CastError)?,
+    })
+}
+
+/// Casts a `Geometry<f64>`, as produced by `geozero`, down (or up) to an arbitrary
+/// [`GeoFloat`] precision, failing with [`CastError`] the moment a coordinate doesn't fit.
+fn cast_geometry<T: GeoFloat>(geometry: Geometry<f64>) -> Result<Geometry<T>, CastError> {
+    use geo::MapCoordsNum;
+    geometry.try_map_coords(|coord| cast_coord(coord))
+}
+
+/// Clips a single segment to `rect` using the Liang-Barsky algorithm, returning the portion of
+/// `start..end` that lies inside `rect`, or `None` if the whole segment falls outside it.
+fn clip_segment_to_rect<T: GeoFloat>(
+    start: Coord<T>,
+    end: Coord<T>,
+    rect: Rect<T>,
+) -> Option<(Coord<T>, Coord<T>)> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let (mut t0, mut t1) = (T::zero(), T::one());
+    let checks = [
+        (-dx, start.x - rect.min().x),
+        (dx, rect.max().x - start.x),
+        (-dy, start.y - rect.min().y),
+        (dy, rect.max().y - start.y),
+    ];
+    for (p, q) in checks {
+        if p == T::zero() {
+            if q < T::zero() {
+                return None;
+            }
+        } else {
+            let r = q / p;
+            if p < T::zero() {
+                if r > t1 {
+                    return None;
+                } else if r > t0 {
+                    t0 = r;
+                }
+            } else if r < t0 {
+                return None;
+            } else if r < t1 {
+                t1 = r;
+            }
+        }
+    }
+    Some((
+        Coord {
+            x: start.x + t0 * dx,
+            y: start.y + t0 * dy,
+        },
+        Coord {
+            x: start.x + t1 * dx,
+            y: start.y + t1 * dy,
+        },
+    ))
+}
+
+/// Clips `linestring` to `rect`, splitting it wherever a segment is clipped away so that the
+/// output never bridges a gap the original linestring didn't have, and stitching contiguous
+/// clipped segments back into single [`LineString`]s.
+fn clip_linestring_to_rect<T: GeoFloat>(
+    linestring: &LineString<T>,
+    rect: Rect<T>,
+) -> Vec<LineString<T>> {
+    let mut pieces = Vec::new();
+    let mut current: Vec<Coord<T>> = Vec::new();
+    for line in linestring.lines() {
+        match clip_segment_to_rect(line.start, line.end, rect) {
+            Some((start, end)) => {
+                if current.last().is_some_and(|&last| last != start) {
+                    if current.len() >= 2 {
+                        pieces.push(LineString::new(std::mem::take(&mut current)));
+                    }
+                    current.clear();
+                }
+                if current.is_empty() {
+                    current.push(start);
+                }
+                current.push(end);
+            }
+            None => {
+                if current.len() >= 2 {
+                    pieces.push(LineString::new(std::mem::take(&mut current)));
+                }
+                current.clear();
+            }
+        }
+    }
+    if current.len() >= 2 {
+        pieces.push(LineString::new(current));
+    }
+    pieces
+}
+
+/// Name of a layer inside a (possibly multi-layer) dataset, e.g. a GeoPackage table.
+pub type LayerName = String;
+
+/// Selects a layer inside a dataset by its name or its positional index, for APIs that
+/// need to address a layer other than the first one.
+pub enum LayerSelector {
+    Name(String),
+    Index(usize),
+}
+
+impl From<&str> for LayerSelector {
+    fn from(name: &str) -> Self {
+        LayerSelector::Name(name.to_owned())
+    }
+}
+
+impl From<usize> for LayerSelector {
+    fn from(index: usize) -> Self {
+        LayerSelector::Index(index)
+    }
+}
+
+fn geometries_from_layer<T: GeoFloat>(
+    layer: &mut gdal::vector::Layer,
+) -> anyhow::Result<Vec<Geometry<T>>> {
+    let mut writer = GeoWriter::new();
+    for feature in layer.features() {
+        let geom = feature.geometry().unwrap();
+        process_geom(geom, &mut writer).with_context(|| {
+            format!(
+                "{} {}",
+                "Failed to parse FID",
+                feature
+                    .fid()
+                    .expect(format!("Failed to get FID of feature {:?}", feature).as_str()),
+            )
+        })?;
+    }
+    let geometry = writer.take_geometry();
+
+    // If layer has more than 1 feature, it will match GeometryCollection.
+    // Otherwise, it might match any of the rest.
+    let geometries: Vec<Geometry<f64>> = if let Some(geometry) = geometry {
+        match geometry {
+            geo::Geometry::GeometryCollection(geometry) => geometry.0,
+            geo::Geometry::MultiLineString(geometry) => vec![geometry.into()],
+            geo::Geometry::MultiPolygon(geometry) => vec![geometry.into()],
+            geo::Geometry::MultiPoint(geometry) => vec![geometry.into()],
+            geo::Geometry::Point(geometry) => vec![geometry.into()],
+            geo::Geometry::LineString(geometry) => vec![geometry.into()],
+            geo::Geometry::Polygon(geometry) => vec![geometry.into()],
+            geo::Geometry::Line(geometry) => vec![geometry.into()],
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Did not expect the received geometry {:?}",
+                    geometry
+                ))
+            }
+        }
+    } else {
+        return Err(anyhow::anyhow!(
+            "Failed to retrieve geometry. Is layer {} empty?",
+            layer.name()
+        ));
+    };
+    geometries
+        .into_iter()
+        .map(|geometry| cast_geometry::<T>(geometry).map_err(anyhow::Error::from))
+        .collect()
+}
+
+/// Parses a batch of WKT strings into geometries, without needing a GDAL-opened dataset on
+/// disk. Useful for running rules over geometries produced in-memory or received over the
+/// wire (e.g. from a web form or another service).
+pub fn from_wkt(wkt: &[&str]) -> anyhow::Result<Vec<Geometry<f64>>> {
+    wkt.iter()
+        .map(|text| {
+            wkt::Wkt::from_str(text)
+                .map_err(|error| anyhow::anyhow!("Failed to parse WKT {text:?}: {error}"))
+                .map(Geometry::<f64>::from)
+        })
+        .collect()
+}
+
+/// Like [`from_wkt`], but for raw WKB bytes (as returned by `ST_AsBinary`/most spatial
+/// databases), pivoting through the same `geozero` `GeoWriter` that [`VectorDataset::to_geo`]
+/// already uses for GDAL features.
+pub fn from_wkb(wkb: &[&[u8]]) -> anyhow::Result<Vec<Geometry<f64>>> {
+    use geozero::wkb::process_wkb_geom;
+    wkb.iter()
+        .map(|bytes| {
+            let mut writer = GeoWriter::new();
+            process_wkb_geom(&mut std::io::Cursor::new(bytes), &mut writer)
+                .with_context(|| "Failed to parse a WKB geometry.")?;
+            writer
+                .take_geometry()
+                .ok_or_else(|| anyhow::anyhow!("WKB bytes did not contain a geometry."))
+        })
+        .collect()
+}
+
+/// Repairs invalid geometries (self-intersecting polygons, broken ring order, and similar
+/// defects) via GDAL's `make_valid`, round-tripping each geometry through GDAL and back.
+/// Backs the `--fix-invalid` preprocessing pass: without it, an invalid input geometry
+/// silently produces garbage topology results instead of a useful error. A single invalid
+/// polygon may split into a `MultiPolygon`, so flatten the result afterward (e.g. with
+/// [`crate::util::flatten_polygons`]) before running single-part rules.
+pub fn fix_invalid(geometries: Vec<Geometry<f64>>) -> anyhow::Result<Vec<Geometry<f64>>> {
+    geometries
+        .into_iter()
+        .map(|geometry| {
+            let gdal_geometry = geometry
+                .to_gdal()
+                .with_context(|| "Failed to convert a geometry to GDAL.")?;
+            let repaired = gdal_geometry
+                .make_valid()
+                .with_context(|| "Failed to repair an invalid geometry.")?;
+            let mut writer = GeoWriter::new();
+            process_geom(&repaired, &mut writer)
+                .with_context(|| "Failed to convert the repaired geometry back to geo-types.")?;
+            writer
+                .take_geometry()
+                .ok_or_else(|| anyhow::anyhow!("make_valid produced no geometry."))
+        })
+        .collect()
+}
+
+/// Restricts which features [`VectorDataset::to_geo`] (and friends) read, pushed down to OGR
+/// before any geometry is materialized so large files aren't fully read just to throw most of
+/// it away.
+pub enum LayerFilter {
+    /// A plain OGR attribute filter, as accepted by `OGR_L_SetAttributeFilter` (e.g.
+    /// `class = 'road'`), applied to the dataset's default layer.
+    Where(String),
+    /// A full OGR SQL query (GDAL's `ExecuteSQL`), replacing the default layer outright.
+    Sql(String),
+}
+
+/// Either a plain [`gdal::vector::Layer`] or the [`gdal::vector::sql::ResultSet`] produced by
+/// an OGR SQL query, so [`VectorDataset`]'s layer-fetching methods can stay agnostic of which
+/// one backs a given [`LayerFilter`].
+enum ActiveLayer<'a> {
+    Plain(gdal::vector::Layer<'a>),
+    Sql(gdal::vector::sql::ResultSet<'a>),
+}
+
+impl<'a> std::ops::Deref for ActiveLayer<'a> {
+    type Target = gdal::vector::Layer<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ActiveLayer::Plain(layer) => layer,
+            ActiveLayer::Sql(result_set) => result_set,
+        }
+    }
+}
+
+impl<'a> std::ops::DerefMut for ActiveLayer<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            ActiveLayer::Plain(layer) => layer,
+            ActiveLayer::Sql(result_set) => result_set,
+        }
+    }
+}
+
+pub struct VectorDataset {
+    dataset: Dataset,
+    filter: Option<LayerFilter>,
+}
 
 impl VectorDataset {
     pub fn new(path: &PathBuf) -> anyhow::Result<Self> {
-        Ok(VectorDataset(open_dataset(path)?))
+        Ok(VectorDataset {
+            dataset: open_dataset(path)?,
+            filter: None,
+        })
     }
 
+    /// Like [`Self::new`], but restricts the features subsequently read through
+    /// [`Self::to_geo`] (and friends) to `filter`.
+    pub fn new_filtered(path: &PathBuf, filter: LayerFilter) -> anyhow::Result<Self> {
+        Ok(VectorDataset {
+            dataset: open_dataset(path)?,
+            filter: Some(filter),
+        })
+    }
+
+    /// Iterates over every layer of the dataset, e.g. every table in a GeoPackage. Ignores
+    /// any [`LayerFilter`], since a SQL filter produces a single virtual layer rather than
+    /// restricting one of the dataset's real layers.
+    pub fn layers(&self) -> impl Iterator<Item = gdal::vector::Layer> {
+        self.dataset.layers()
+    }
+
+    /// Resolves the dataset's default layer, applying this dataset's [`LayerFilter`] (if any)
+    /// before any geometry is read out of it.
+    fn active_layer(&self) -> anyhow::Result<ActiveLayer> {
+        match &self.filter {
+            Some(LayerFilter::Sql(sql)) => {
+                let result_set = self
+                    .dataset
+                    .execute_sql(sql, None, gdal::vector::sql::Dialect::default())
+                    .with_context(|| format!("Failed to execute OGR SQL query {sql:?}."))?
+                    .ok_or_else(|| anyhow::anyhow!("SQL query {sql:?} returned no result set."))?;
+                Ok(ActiveLayer::Sql(result_set))
+            }
+            Some(LayerFilter::Where(where_clause)) => {
+                let mut layer = self.dataset.layers().next().expect(
+                    format!("Dataset {} has no layers.", self.dataset.description()?).as_str(),
+                );
+                layer.set_attribute_filter(where_clause).with_context(|| {
+                    format!("Failed to apply attribute filter {where_clause:?}.")
+                })?;
+                Ok(ActiveLayer::Plain(layer))
+            }
+            None => {
+                let layer = self.dataset.layers().next().expect(
+                    format!("Dataset {} has no layers.", self.dataset.description()?).as_str(),
+                );
+                Ok(ActiveLayer::Plain(layer))
+            }
+        }
+    }
+
+    /// Reads every feature of the dataset's first layer as `f64` geometries, restricted by this
+    /// dataset's [`LayerFilter`] (if any).
+    /// Kept for source compatibility; prefer [`Self::to_geo_as`] when working with `f32` data.
     pub fn to_geo(&self) -> anyhow::Result<Vec<Geometry<f64>>> {
-        let mut layer = self
-            .0
-            .layers()
-            .next()
-            .expect(format!("Dataset {} has no layers.", self.0.description()?).as_str());
-        let mut writer = GeoWriter::new();
+        self.to_geo_as::<f64>()
+    }
+
+    /// Like [`Self::to_geo`], but hands each feature's geometry to `f` as it's read instead of
+    /// collecting every geometry into a `Vec` first. Backs `--streaming` runs against
+    /// out-of-core datasets that don't fit comfortably in memory all at once.
+    pub fn for_each_geometry<T: GeoFloat>(
+        &self,
+        mut f: impl FnMut(Geometry<T>) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let mut layer = self.active_layer()?;
         for feature in layer.features() {
-            let geom = feature.geometry().unwrap();
+            let geom = feature
+                .geometry()
+                .ok_or_else(|| anyhow::anyhow!("Feature has no geometry."))?;
+            let mut writer = GeoWriter::new();
             process_geom(geom, &mut writer).with_context(|| {
-                format!(
-                    "{} {}",
-                    "Failed to parse FID",
-                    feature
-                        .fid()
-                        .expect(format!("Failed to get FID of feature {:?}", feature).as_str()),
-                )
+                format!("Failed to parse FID {}", feature.fid().unwrap_or_default())
             })?;
+            let geometry = writer
+                .take_geometry()
+                .ok_or_else(|| anyhow::anyhow!("Feature produced no geometry."))?;
+            f(cast_geometry::<T>(geometry)?)?;
         }
-        let geometry = writer.take_geometry();
-
-        // If layer has more than 1 feature, it will match GeometryCollection.
-        // Otherwise, it might match any of the rest.
-        if let Some(geometry) = geometry {
-            match geometry {
-                geo::Geometry::GeometryCollection(geometry) => Ok(geometry.0),
-                geo::Geometry::MultiLineString(geometry) => Ok(vec![geometry.into()]),
-                geo::Geometry::MultiPolygon(geometry) => Ok(vec![geometry.into()]),
-                geo::Geometry::MultiPoint(geometry) => Ok(vec![geometry.into()]),
-                geo::Geometry::Point(geometry) => Ok(vec![geometry.into()]),
-                geo::Geometry::LineString(geometry) => Ok(vec![geometry.into()]),
-                geo::Geometry::Polygon(geometry) => Ok(vec![geometry.into()]),
-                geo::Geometry::Line(geometry) => Ok(vec![geometry.into()]),
-                _ => Err(anyhow::anyhow!(
-                    "Did not expect the received geometry {:?}",
-                    geometry
-                )),
-            }
-        } else {
-            Err(anyhow::anyhow!(
-                "Failed to retrieve geometry. Is the dataset {} empty?",
-                self.0.description()?
-            ))
-        }
+        Ok(())
     }
 
-    pub fn srs(&self) -> anyhow::Result<Option<SpatialRef>> {
-        let layer = self
-            .0
+    /// Like [`Self::to_geo`], but converts coordinates to an arbitrary [`GeoFloat`] precision
+    /// as they come out of the `GeoWriter`, so callers working with `f32` data (large tiles,
+    /// memory-constrained runs) don't pay for `f64` coordinates they don't need.
+    pub fn to_geo_as<T: GeoFloat>(&self) -> anyhow::Result<Vec<Geometry<T>>> {
+        let mut layer = self.active_layer()?;
+        geometries_from_layer(&mut layer)
+    }
+
+    /// Reads every feature of the requested layer (by name or index), rather than silently
+    /// assuming the dataset has only one. Returns an error, instead of panicking, when the
+    /// layer doesn't exist. Ignores any [`LayerFilter`]; see [`Self::to_geo`] for that.
+    pub fn to_geo_layer(&self, layer: impl Into<LayerSelector>) -> anyhow::Result<Vec<Geometry<f64>>> {
+        self.to_geo_layer_as::<f64>(layer)
+    }
+
+    /// Like [`Self::to_geo_layer`], generic over the target [`GeoFloat`] precision.
+    pub fn to_geo_layer_as<T: GeoFloat>(
+        &self,
+        layer: impl Into<LayerSelector>,
+    ) -> anyhow::Result<Vec<Geometry<T>>> {
+        let mut layer = match layer.into() {
+            LayerSelector::Index(index) => self.dataset.layer(index).with_context(|| {
+                format!(
+                    "Dataset {} has no layer at index {index}.",
+                    self.dataset.description().unwrap_or_default()
+                )
+            })?,
+            LayerSelector::Name(name) => self.dataset.layer_by_name(&name).with_context(|| {
+                format!(
+                    "Dataset {} has no layer named {name:?}.",
+                    self.dataset.description().unwrap_or_default()
+                )
+            })?,
+        };
+        geometries_from_layer(&mut layer)
+    }
+
+    /// Reads every layer in the dataset, pairing each layer's geometries with its name. This
+    /// is the multi-layer counterpart of [`Self::to_geo`], useful for validating every table
+    /// of a GeoPackage in one pass. Ignores any [`LayerFilter`]; see [`Self::to_geo`] for that.
+    pub fn to_geo_all(&self) -> anyhow::Result<Vec<(LayerName, Vec<Geometry<f64>>)>> {
+        self.to_geo_all_as::<f64>()
+    }
+
+    /// Like [`Self::to_geo_all`], generic over the target [`GeoFloat`] precision.
+    pub fn to_geo_all_as<T: GeoFloat>(&self) -> anyhow::Result<Vec<(LayerName, Vec<Geometry<T>>)>> {
+        self.dataset
             .layers()
-            .next()
-            .expect(format!("Dataset {} has no layers.", self.0.description()?).as_str());
+            .map(|mut layer| {
+                let name = layer.name();
+                Ok((name, geometries_from_layer(&mut layer)?))
+            })
+            .collect()
+    }
+
+    pub fn srs(&self) -> anyhow::Result<Option<SpatialRef>> {
+        let layer = self.active_layer()?;
         Ok(layer.spatial_ref())
     }
 
-    pub fn compare_srs(&self, other: &VectorDataset) -> anyhow::Result<()> {
-        if self.srs()? != other.srs()? {
-            panic!(
-                "{} does not have the same spatial reference system as {}",
-                self.0.description().unwrap(),
-                other.0.description().unwrap()
-            )
-        }
-        Ok(())
+    /// Returns the exact number of features in the active layer, honoring any `--where`/`--sql`
+    /// filter set via [`LayerFilter`]. Used to report how many features a rule actually read.
+    pub fn feature_count(&self) -> anyhow::Result<u64> {
+        Ok(self.active_layer()?.feature_count())
+    }
+
+    /// Compares this dataset's CRS against `other`'s, without deciding what to do about a
+    /// mismatch — that's left to the caller (see [`crate::util::validate_srs`], which fails
+    /// outright, and [`crate::util::harmonize_srs`], which can reproject instead).
+    pub fn compare_srs(&self, other: &VectorDataset) -> anyhow::Result<SRSComparison> {
+        let (srs1, srs2) = (self.srs()?, other.srs()?);
+        Ok(match (srs1, srs2) {
+            (Some(srs1), Some(srs2)) => {
+                if srs1.to_wkt()? == srs2.to_wkt()? {
+                    SRSComparison::Same
+                } else {
+                    SRSComparison::Different(srs1.to_wkt()?, srs2.to_wkt()?)
+                }
+            }
+            _ => SRSComparison::Missing,
+        })
     }
 }
 
+/// Outcome of comparing two datasets' spatial reference systems.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SRSComparison {
+    /// Both datasets share the same CRS.
+    Same,
+    /// At least one dataset has no CRS, so there is nothing to reconcile.
+    Missing,
+    /// The datasets have different, well-known CRS, carried here as WKT.
+    Different(String, String),
+}
+
 pub trait GeometryType<T: GeoFloat> {}
 
 impl<T: GeoFloat> GeometryType<T> for Geometry<T> {}
@@ -100,7 +490,7 @@ impl<T: GeoFloat> GeometryType<T> for LineString<T> {}
 impl<T: GeoFloat> GeometryType<T> for MultiPolygon<T> {}
 impl<T: GeoFloat> GeometryType<T> for Polygon<T> {}
 
-#[derive(Hash, PartialEq, Eq, Debug)]
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
 pub enum TopologyError<T: GeoFloat> {
     Point(Vec<Point<T>>),
     LineString(Vec<LineString<T>>),
@@ -111,7 +501,7 @@ pub enum TopologyError<T: GeoFloat> {
 }
 
 impl<T: GeoFloat> TopologyError<T> {
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         match self {
             TopologyError::LineString(vec) => vec.len(),
             TopologyError::MultiLineString(vec) => vec.len(),
@@ -137,6 +527,60 @@ impl<T: GeoFloat> Display for TopologyError<T> {
 }
 
 impl<T: GeoFloat> TopologyError<T> {
+    /// Serializes every geometry in this error to EWKB, tagging it with `srid` when given.
+    pub fn to_ewkb(&self, srid: Option<u32>) -> Vec<Vec<u8>> {
+        use crate::ewkb::ToEwkb;
+        match self {
+            Self::Point(points) => points.iter().map(|point| point.to_ewkb(srid)).collect(),
+            Self::LineString(linestrings) => linestrings
+                .iter()
+                .map(|linestring| linestring.to_ewkb(srid))
+                .collect(),
+            Self::Polygon(polygons) => polygons
+                .iter()
+                .map(|polygon| polygon.to_ewkb(srid))
+                .collect(),
+            Self::MultiPoint(multipoints) => multipoints
+                .iter()
+                .map(|multipoint| multipoint.to_ewkb(srid))
+                .collect(),
+            Self::MultiLineString(multilinestrings) => multilinestrings
+                .iter()
+                .map(|multilinestring| multilinestring.to_ewkb(srid))
+                .collect(),
+            Self::MultiPolygon(multipolygons) => multipolygons
+                .iter()
+                .map(|multipolygon| multipolygon.to_ewkb(srid))
+                .collect(),
+        }
+    }
+
+    /// Serializes every geometry in this error to WKT, for lightweight reporting when no
+    /// GDAL output dataset is wanted. The `to_gdal`/[`Self::export`] path is untouched.
+    pub fn to_wkt(&self) -> Vec<String> {
+        use wkt::ToWkt;
+        match self {
+            Self::Point(points) => points.iter().map(|point| point.wkt_string()).collect(),
+            Self::LineString(linestrings) => linestrings
+                .iter()
+                .map(|linestring| linestring.wkt_string())
+                .collect(),
+            Self::Polygon(polygons) => polygons.iter().map(|polygon| polygon.wkt_string()).collect(),
+            Self::MultiPoint(multipoints) => multipoints
+                .iter()
+                .map(|multipoint| multipoint.wkt_string())
+                .collect(),
+            Self::MultiLineString(multilinestrings) => multilinestrings
+                .iter()
+                .map(|multilinestring| multilinestring.wkt_string())
+                .collect(),
+            Self::MultiPolygon(multipolygons) => multipolygons
+                .iter()
+                .map(|multipolygon| multipolygon.wkt_string())
+                .collect(),
+        }
+    }
+
     fn to_gdal(&self) -> anyhow::Result<Vec<gdal::vector::Geometry>, GdalError> {
         let geometries: anyhow::Result<Vec<_>, GdalError> = match self {
             Self::Point(points) => points.into_iter().map(|point| point.to_gdal()).collect(),
@@ -163,13 +607,83 @@ impl<T: GeoFloat> TopologyError<T> {
         };
         Ok(geometries?)
     }
+    /// Restricts this error's geometries to `extent`, the way [`crate::util::filter_by_extent`]
+    /// restricts input geometries: points outside `extent` are dropped, while line and polygon
+    /// geometries are clipped to it, so an extent-restricted run never reports a conflict
+    /// geometry extending past the window the user asked to check.
+    pub fn clip_to_extent(self, extent: Rect<T>) -> Self {
+        match self {
+            Self::Point(points) => Self::Point(
+                points
+                    .into_iter()
+                    .filter(|point| point.intersects(&extent))
+                    .collect(),
+            ),
+            Self::MultiPoint(multipoints) => Self::MultiPoint(
+                multipoints
+                    .into_iter()
+                    .filter_map(|multipoint| {
+                        let points: Vec<Point<T>> = multipoint
+                            .into_iter()
+                            .filter(|point| point.intersects(&extent))
+                            .collect();
+                        (!points.is_empty()).then(|| MultiPoint::new(points))
+                    })
+                    .collect(),
+            ),
+            Self::LineString(linestrings) => Self::LineString(
+                linestrings
+                    .into_iter()
+                    .flat_map(|linestring| clip_linestring_to_rect(&linestring, extent))
+                    .collect(),
+            ),
+            Self::MultiLineString(multilinestrings) => Self::MultiLineString(
+                multilinestrings
+                    .into_iter()
+                    .filter_map(|multilinestring| {
+                        let pieces: Vec<LineString<T>> = multilinestring
+                            .into_iter()
+                            .flat_map(|linestring| clip_linestring_to_rect(&linestring, extent))
+                            .collect();
+                        (!pieces.is_empty()).then(|| MultiLineString::new(pieces))
+                    })
+                    .collect(),
+            ),
+            Self::Polygon(polygons) => Self::Polygon(
+                polygons
+                    .into_iter()
+                    .flat_map(|polygon| polygon.intersection(&extent.to_polygon()).0)
+                    .collect(),
+            ),
+            Self::MultiPolygon(multipolygons) => Self::MultiPolygon(
+                multipolygons
+                    .into_iter()
+                    .filter_map(|multipolygon| {
+                        let polygons: Vec<Polygon<T>> = multipolygon
+                            .into_iter()
+                            .flat_map(|polygon| polygon.intersection(&extent.to_polygon()).0)
+                            .collect();
+                        (!polygons.is_empty()).then(|| MultiPolygon::new(polygons))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
     pub fn export(&self, config: ExportConfig) -> anyhow::Result<()> {
         let ExportConfig {
             rule_name,
             output,
             mut options,
             mut dataset,
+            format,
         } = config;
+        if let OutputFormat::Wkt = format {
+            let output = output
+                .with_context(|| "OutputFormat::Wkt requires an output path to write to.")?;
+            return std::fs::write(output, self.to_wkt().join("\n"))
+                .with_context(|| format!("Failed to write WKT to {output:?}."));
+        }
         // We make this created_dataset object to store the
         // created dataset. This makes the possibly created dataset
         // live long enough.
@@ -250,6 +764,9 @@ pub struct ExportConfig<'a> {
     pub output: Option<&'a PathBuf>,
     pub options: LayerOptions<'a>,
     pub dataset: Option<&'a mut Dataset>,
+    /// Whether [`TopologyError::export`] writes through GDAL (the default) or as plain
+    /// [`crate::util::geometries_to_wkt`] text, bypassing GDAL entirely.
+    pub format: OutputFormat,
 }
 
 impl<'a> Default for ExportConfig<'a> {
@@ -261,6 +778,7 @@ impl<'a> Default for ExportConfig<'a> {
                 ..Default::default()
             },
             dataset: None,
+            format: OutputFormat::default(),
         }
     }
 }
@@ -275,6 +793,7 @@ impl<'a> Clone for ExportConfig<'a> {
             rule_name: self.rule_name.clone(),
             output: self.output.clone(),
             options: self.options.clone(),
+            format: self.format,
             ..Default::default()
         }
     }
@@ -395,7 +914,59 @@ impl<T: GeoFloat> TopologyResults<T> {
     }
 }
 
+/// Qualifies a rule name with the layer it ran against, so a batch produced by
+/// [`VectorDataset::to_geo_all`] can run every rule against every layer and still tell the
+/// results apart in [`TopologyResults`], the same way `main.rs` already prefixes rule names
+/// with a running index for `Command::Interactive`.
+pub fn layer_qualified_rule_name(layer: &LayerName, rule_name: &RuleName) -> RuleName {
+    format!("{layer}/{rule_name}")
+}
+
 impl<T: GeoFloat> TopologyResults<T> {
+    /// Serializes every error geometry across all rules to EWKB, pairing each one with the
+    /// name of the rule that produced it. `srid` is stamped into every geometry's header;
+    /// pass the dataset's own SRID (via [`VectorDataset::srs`]) when the caller doesn't
+    /// supply one.
+    pub fn to_ewkb(&self, srid: Option<u32>) -> Vec<(RuleName, Vec<u8>)> {
+        self.0
+            .iter()
+            .flat_map(|(rule_name, result)| match result {
+                TopologyResult::Valid => Vec::new(),
+                TopologyResult::Errors(errors) => errors
+                    .iter()
+                    .flat_map(|error| error.to_ewkb(srid))
+                    .map(|bytes| (rule_name.clone(), bytes))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Inserts every error geometry into `table` in the PostGIS database reachable at
+    /// `conn_str`, tagging each row with the rule that produced it. The table must already
+    /// have a `geom` geometry column and a `rule` text column, mirroring the `rule` field
+    /// written by [`TopologyError::export`] into GeoPackage layers.
+    #[cfg(feature = "postgis")]
+    pub fn export_postgis(&self, conn_str: &str, table: &str, srid: Option<u32>) -> anyhow::Result<()> {
+        validate_table_identifier(table)?;
+        let mut client = postgres::Client::connect(conn_str, postgres::NoTls)
+            .with_context(|| format!("Failed to connect to {conn_str}."))?;
+        let mut transaction = client
+            .transaction()
+            .with_context(|| "Failed to start a PostGIS transaction.")?;
+        for (rule_name, ewkb) in self.to_ewkb(srid) {
+            transaction
+                .execute(
+                    &format!("INSERT INTO {table} (rule, geom) VALUES ($1, $2)"),
+                    &[&rule_name, &ewkb],
+                )
+                .with_context(|| format!("Failed to insert a {rule_name} error into {table}."))?;
+        }
+        transaction
+            .commit()
+            .with_context(|| "Failed to commit the PostGIS transaction.")?;
+        Ok(())
+    }
+
     pub fn export(self, output: &PathBuf) -> anyhow::Result<()> {
         let driver = gdal::DriverManager::get_driver_by_name(
             &GdalDrivers.infer_driver_name("gpkg").unwrap().0,
@@ -426,6 +997,27 @@ impl<T: GeoFloat> TopologyResults<T> {
     }
 }
 
+/// Rejects anything but a bare SQL identifier (ASCII letters, digits and underscores, not
+/// starting with a digit), so [`TopologyResults::export_postgis`] can safely interpolate
+/// `--postgis-table` into a `format!`-built statement instead of passing it through GDAL/
+/// `postgres`'s own identifier quoting.
+#[cfg(feature = "postgis")]
+fn validate_table_identifier(table: &str) -> anyhow::Result<()> {
+    let mut chars = table.chars();
+    let starts_ok = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_');
+    let rest_ok = chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if starts_ok && rest_ok {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "{table:?} is not a valid table name: expected ASCII letters, digits and \
+             underscores, not starting with a digit."
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -17,6 +17,22 @@ mod args {
         Ok(s[pos + 1..].parse()?)
     }
 
+    /// Parses a `xmin,ymin,xmax,ymax` bounding-rectangle flag value into a [`geo::Rect`].
+    fn parse_extent(s: &str) -> Result<geo::Rect<f64>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let parts: Vec<f64> = s
+            .split(',')
+            .map(|part| part.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("invalid extent {s:?}: expected `xmin,ymin,xmax,ymax`"))?;
+        match parts[..] {
+            [xmin, ymin, xmax, ymax] => Ok(geo::Rect::new(
+                geo::Coord { x: xmin, y: ymin },
+                geo::Coord { x: xmax, y: ymax },
+            )),
+            _ => Err(format!("invalid extent {s:?}: expected `xmin,ymin,xmax,ymax`").into()),
+        }
+    }
+
     #[derive(Debug, Parser)]
     #[clap(author, version, about)]
     pub struct TopologyCheckerArgs {
@@ -32,10 +48,81 @@ mod args {
         #[clap(long, short, action)]
         /// Print elapsed time.
         pub elapsed: bool,
+        #[clap(long, value_enum, default_value_t = SrsMode::Strict)]
+        /// How to handle two input datasets with a different CRS: fail (`strict`, the
+        /// default) or reproject the second dataset into the first one's CRS (`reproject`).
+        pub srs_mode: SrsMode,
+        #[clap(long, action)]
+        /// Repair self-intersecting/ring-order-broken line and polygon inputs with GDAL's
+        /// `make_valid` before running any rule, instead of letting invalid geometries
+        /// silently produce garbage topology results.
+        pub fix_invalid: bool,
+        #[clap(long = "where")]
+        /// OGR attribute filter (e.g. `class = 'road'`) applied to every input and secondary
+        /// dataset before reading geometries, so a rule can target a subset of a layer
+        /// without pre-clipping it. Ignored when `--sql` is also given.
+        pub attribute_filter: Option<String>,
+        #[clap(long = "sql")]
+        /// OGR SQL query applied to every input and secondary dataset in place of its default
+        /// layer. Takes precedence over `--where` if both are given.
+        pub sql: Option<String>,
+        #[clap(long, action)]
+        /// Process the input layer one feature at a time instead of reading it fully into
+        /// memory, for out-of-core datasets. Only `geometry must-not-be-multipart` and
+        /// `utilities explode-linestrings` support it today; every other command ignores the
+        /// flag and falls back to the in-memory path (pairwise rules like `must-not-overlap`
+        /// need every geometry resident to build their spatial index, and merging linestrings
+        /// needs the whole layer to find adjacent segments).
+        pub streaming: bool,
+        #[clap(long)]
+        /// Write a machine-readable JSON report (rule name, input layer, SRS, feature count,
+        /// violation count and the output path) to this file once every rule in the session
+        /// has run, so QA pipelines can assert "zero violations" without scraping stdout.
+        pub report: Option<PathBuf>,
+        #[clap(long, value_enum, default_value_t = OutputFormat::Gdal)]
+        /// Format to write error geometries in: a GDAL-writeable dataset (`gdal`, the
+        /// default), or plain WKT text (`wkt`) that doesn't need a GDAL driver at all.
+        pub output_format: OutputFormat,
+        #[clap(long, value_parser = parse_extent)]
+        /// Restrict the check to a `xmin,ymin,xmax,ymax` bounding rectangle, as QGIS' topology
+        /// plugin does with its "only in current map view" option: input geometries outside the
+        /// extent are skipped, and reported conflict geometries are clipped to it, so validating
+        /// a tile of a large dataset doesn't require re-running the whole layer.
+        pub extent: Option<geo::Rect<f64>>,
         #[clap(subcommand)]
         pub command: Command,
     }
 
+    #[derive(Debug, Clone, Copy, clap::ValueEnum)]
+    pub enum SrsMode {
+        Strict,
+        Reproject,
+    }
+
+    impl From<SrsMode> for topology_checker::util::SrsMode {
+        fn from(mode: SrsMode) -> Self {
+            match mode {
+                SrsMode::Strict => topology_checker::util::SrsMode::Strict,
+                SrsMode::Reproject => topology_checker::util::SrsMode::Reproject,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, clap::ValueEnum)]
+    pub enum OutputFormat {
+        Gdal,
+        Wkt,
+    }
+
+    impl From<OutputFormat> for topology_checker::util::OutputFormat {
+        fn from(format: OutputFormat) -> Self {
+            match format {
+                OutputFormat::Gdal => topology_checker::util::OutputFormat::Gdal,
+                OutputFormat::Wkt => topology_checker::util::OutputFormat::Wkt,
+            }
+        }
+    }
+
     #[derive(Debug, Serialize, PartialEq, Deserialize, Subcommand)]
     #[serde(rename_all = "lowercase")]
     pub enum Command {
@@ -56,6 +143,17 @@ mod args {
             #[arg(value_parser = parse_key_val::<String, PathBuf>)]
             output: PathBuf,
         },
+        /// Run a whole batch of rules (point/line/polygon/geometry commands) read from a
+        /// JSON or YAML config file, exactly like interactive mode but without the prompt.
+        #[command(arg_required_else_help(true))]
+        RunConfig {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Path to a JSON (or YAML) file containing an array of rule commands
+            config: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Where to write the combined results
+            output: PathBuf,
+        },
     }
 
     #[derive(Debug, PartialEq, Args, Serialize, Deserialize)]
@@ -123,6 +221,18 @@ mod args {
             /// The outside points
             outside: Option<PathBuf>,
         },
+        #[command(arg_required_else_help(true))]
+        MustNotHaveDuplicates {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input points
+            points: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Output duplicate points
+            duplicates: Option<PathBuf>,
+            #[clap(long, default_value_t = 0.0)]
+            /// Points within this distance of each other are considered duplicates.
+            tolerance: f64,
+        },
     }
 
     #[derive(Debug, Subcommand, PartialEq, Serialize, Deserialize)]
@@ -148,6 +258,10 @@ mod args {
             #[arg(value_parser = parse_key_val::<String, PathBuf>)]
             /// The output overlaps
             overlaps: Option<PathBuf>,
+            #[clap(long)]
+            /// Collinearity tolerance for detecting overlapping (rather than merely crossing)
+            /// segments. Exact collinearity rarely holds in real data.
+            tolerance: Option<f64>,
         },
         #[command(arg_required_else_help(true))]
         MustNotSelfOverlap {
@@ -191,6 +305,35 @@ mod args {
             /// Output outside lines
             outside_lines: Option<PathBuf>,
         },
+        #[command(arg_required_else_help(true))]
+        MustNotHaveZeroLength {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input lines
+            lines: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Output duplicate vertices
+            vertices: Option<PathBuf>,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Output zero-length lines, and lines with duplicate vertices
+            zero_length: Option<PathBuf>,
+            #[clap(long, default_value_t = 0.0)]
+            /// Lines no longer than this, and consecutive vertices no farther apart than this,
+            /// are considered degenerate.
+            tolerance: f64,
+        },
+        #[command(arg_required_else_help(true))]
+        MustNotHaveDuplicates {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input lines
+            lines: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Output duplicate lines
+            duplicates: Option<PathBuf>,
+            #[clap(long, default_value_t = 0.0)]
+            /// Lines within this distance of each other, vertex-by-vertex, are considered
+            /// duplicates.
+            tolerance: f64,
+        },
     }
 
     #[derive(Debug, Subcommand, PartialEq, Serialize, Deserialize)]
@@ -226,6 +369,42 @@ mod args {
             /// Output gaps
             gaps: Option<PathBuf>,
         },
+        #[command(arg_required_else_help(true))]
+        MustNotHaveZeroLength {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input polygons
+            polygons: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Output sliver polygons
+            slivers: Option<PathBuf>,
+            #[clap(long, default_value_t = 0.0)]
+            /// Polygons with an area at or below this are considered slivers.
+            tolerance: f64,
+        },
+        #[command(arg_required_else_help(true))]
+        MustNotHaveDuplicates {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input polygons
+            polygons: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Output duplicate polygons
+            duplicates: Option<PathBuf>,
+            #[clap(long, default_value_t = 0.0)]
+            /// Polygons whose bounding boxes and vertices are all within this distance of each
+            /// other are considered duplicates.
+            tolerance: f64,
+        },
+        /// Validates the OGC rule that a MultiPolygon's constituent polygons must not have
+        /// overlapping interiors and may only touch at finitely many boundary points.
+        #[command(arg_required_else_help(true))]
+        MustNotOverlapWithinMultipolygon {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input polygons and multipolygons
+            polygons: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// The output overlapping regions
+            overlaps: Option<PathBuf>,
+        },
     }
 
     #[derive(Debug, Subcommand, PartialEq, Serialize, Deserialize)]
@@ -240,6 +419,23 @@ mod args {
             /// The output multipart geometries
             multiparts: Option<PathBuf>,
         },
+        #[command(arg_required_else_help(true))]
+        MustRelate {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input geometries
+            geometries: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// Input geometries to check against
+            other: PathBuf,
+            #[clap(long)]
+            /// DE-9IM pattern every geometry must match against each of its intersection
+            /// candidates in `other`, e.g. "T*F**F***" for within, "T*T***T**" for overlaps.
+            /// See `geo::IntersectionMatrix::matches` for the pattern grammar.
+            pattern: String,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// The output non-matching geometries
+            non_matching: Option<PathBuf>,
+        },
     }
 
     #[derive(Debug, Subcommand, PartialEq, Serialize, Deserialize)]
@@ -280,6 +476,35 @@ mod args {
             /// The output exploded lines
             lines: PathBuf,
         },
+        /// Repair invalid geometries (self-intersecting polygons, broken ring order, etc.)
+        /// with GDAL's `make_valid` and write the result. A single invalid polygon may split
+        /// into a `MultiPolygon`, which is re-flattened before writing.
+        #[command(arg_required_else_help(true))]
+        MakeValid {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// The input geometries
+            geometries: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// The output repaired geometries
+            valid: PathBuf,
+        },
+        /// Export a linestring layer's connectivity as a GraphViz DOT graph, so dangles and
+        /// intersections can be inspected visually.
+        #[command(arg_required_else_help(true))]
+        BuildNetworkGraph {
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// The input linestrings
+            lines: PathBuf,
+            #[arg(value_parser = parse_key_val::<String, PathBuf>)]
+            /// The output .dot file
+            dot: PathBuf,
+            #[clap(long, action)]
+            /// Emit a `digraph` with `->` edges instead of an undirected `graph` with `--` edges.
+            directed: bool,
+            #[clap(long, default_value_t = 1e-9)]
+            /// Grid tolerance used to snap coincident endpoints onto the same node.
+            tolerance: f64,
+        },
     }
 }
 
@@ -291,20 +516,26 @@ use args::{
 use clap::Parser;
 use colored::Colorize;
 use gdal::{vector::ToGdal, LayerOptions};
+use geo::Geometry;
 use rayon::{iter::ParallelBridge, iter::ParallelIterator};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use topology_checker::{
     algorithm::merge_linestrings,
     rule::{
-        MustBeInside, MustNotBeMultipart, MustNotHaveDangles, MustNotHaveGaps, MustNotIntersect,
-        MustNotOverlap, MustNotSelfOverlap,
+        MustBeInside, MustNotBeMultipart, MustNotHaveDangles, MustNotHaveDuplicates,
+        MustNotHaveGaps, MustNotHaveZeroLength, MustNotIntersect, MustNotOverlap,
+        MustNotOverlapWithTolerance, MustNotOverlapWithinMultipolygon, MustNotSelfOverlap,
+        MustRelate,
     },
     util::{
-        explode_linestrings, flatten_linestrings, flatten_points, flatten_polygons,
-        geometries_to_file, validate_srs, GdalDrivers,
+        create_dataset, explode_linestrings, filter_by_extent, flatten_linestrings,
+        flatten_points, flatten_polygons, geometries_to_file, harmonize_srs, is_polygon,
+        GdalDrivers, NetworkGraph, PartitionedPolygons,
     },
-    ExportConfig, TopologyError, TopologyResult, TopologyResults, VectorDataset,
+    fix_invalid, ExportConfig, LayerFilter, TopologyError, TopologyResult, TopologyResults,
+    VectorDataset,
 };
 #[cfg(windows)]
 fn enable_colors_for_windows() {
@@ -326,8 +557,13 @@ fn main() -> anyhow::Result<()> {
     }
     match args.command {
         Command::Interactive { .. } => interactive_mode(args)?,
+        Command::RunConfig { .. } => run_config(args)?,
         Command::Geometry(_) | Command::Line(_) | Command::Point(_) | Command::Polygon(_) => {
-            parse_rules(args, true)?;
+            let report_path = args.report.clone();
+            let (_, report) = parse_rules(args, true)?;
+            if let Some(report_path) = report_path {
+                write_report(&[report], &report_path)?;
+            }
         }
         Command::Utilities(_) | Command::GdalDrivers(_) => parse_utils(args)?,
     }
@@ -337,8 +573,50 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Opens `path` as a [`VectorDataset`], pushing down `--sql` (or, failing that, `--where`)
+/// so the dataset's layer is restricted before any geometry is read. `--sql` wins if both
+/// are given, since it replaces the layer outright.
+fn open_dataset_filtered(path: &PathBuf, args: &TopologyCheckerArgs) -> anyhow::Result<VectorDataset> {
+    match (&args.sql, &args.attribute_filter) {
+        (Some(sql), _) => VectorDataset::new_filtered(path, LayerFilter::Sql(sql.clone())),
+        (None, Some(where_clause)) => {
+            VectorDataset::new_filtered(path, LayerFilter::Where(where_clause.clone()))
+        }
+        (None, None) => VectorDataset::new(path, args.use_gdal),
+    }
+}
+
+/// Clips `error` to `extent` when one is given, mirroring how `--extent` already restricts
+/// which input geometries are checked: the reported conflict geometries shouldn't reach past
+/// the window the user asked to check either.
+fn clip_error_to_extent(error: &TopologyError<f64>, extent: Option<geo::Rect<f64>>) -> TopologyError<f64> {
+    match extent {
+        Some(extent) => error.clone().clip_to_extent(extent),
+        None => error.clone(),
+    }
+}
+
 type RuleName = String;
 
+/// One rule invocation's entry in a `--report` document: which rule ran, what it read, and
+/// what it found. Written by [write_report] once every rule in the session has run.
+#[derive(Debug, Clone, Serialize)]
+struct ReportEntry {
+    rule: RuleName,
+    input: PathBuf,
+    srs: Option<String>,
+    total_features: u64,
+    violations: usize,
+    output: Option<PathBuf>,
+}
+
+/// Writes every rule's [ReportEntry] to `path` as a pretty-printed JSON array.
+fn write_report(entries: &[ReportEntry], path: &PathBuf) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(entries)
+        .with_context(|| "Failed to serialize the run report to JSON.")?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write the report to {path:?}."))
+}
+
 /// Used to get the serialized rule name from a [Command] object.
 /// TODO: Find a better solution for this, it's really ugly.
 fn rule_name(command: &Command) -> anyhow::Result<RuleName> {
@@ -450,35 +728,94 @@ fn interactive_mode(args: TopologyCheckerArgs) -> anyhow::Result<()> {
             Err(error) => eprintln!("{}", error.to_string().red()),
         }
     }
+    let output = match &args.command {
+        Command::Interactive { output } => output.clone(),
+        _ => unreachable!(),
+    };
+    execute_commands(&args, commands, &output)
+}
+
+/// Runs every command in `commands` in parallel, numbering each result with [rule_name] the
+/// same way [interactive_mode] does, then writes the combined [TopologyResults] to `output`.
+/// If `--report` is set, every command's [ReportEntry] is aggregated into a single JSON array
+/// and written once the whole session has finished, rather than once per command.
+fn execute_commands(
+    args: &TopologyCheckerArgs,
+    commands: Vec<Command>,
+    output: &PathBuf,
+) -> anyhow::Result<()> {
     // Result implements FromIterator and thus we can move it outside
     let results: anyhow::Result<Vec<_>> = commands
         .into_iter()
         .enumerate()
         .par_bridge()
         .map(
-            |(mut index, command)| -> anyhow::Result<(String, TopologyResult<_>)> {
+            |(mut index, command)| -> anyhow::Result<(String, TopologyResult<_>, ReportEntry)> {
                 index += 1;
                 let args = TopologyCheckerArgs {
                     gdal_driver: args.gdal_driver.clone(),
                     use_gdal: args.use_gdal,
                     epsg: args.epsg,
                     elapsed: args.elapsed,
+                    srs_mode: args.srs_mode,
+                    fix_invalid: args.fix_invalid,
+                    attribute_filter: args.attribute_filter.clone(),
+                    sql: args.sql.clone(),
+                    streaming: args.streaming,
+                    report: args.report.clone(),
+                    output_format: args.output_format,
+                    extent: args.extent,
                     command: command,
                 };
                 let rule_name = format!("{}-{}", index, rule_name(&args.command)?);
-                Ok((rule_name, parse_rules(args, false)?))
+                let (result, mut report) = parse_rules(args, false)?;
+                // Use the same numbered name as the `rule` attribute on exported/aggregated
+                // output features below, so a `--report` session that runs the same rule more
+                // than once can still correlate each JSON entry back to its features.
+                report.rule = rule_name.clone();
+                Ok((rule_name, result, report))
             },
         )
         .collect();
-    let topology_results = TopologyResults::new(results?);
-    match args.command {
-        Command::Interactive { output } => topology_results.export(&output, args.epsg)?,
-        _ => unreachable!(),
+    let results = results?;
+    if let Some(report_path) = &args.report {
+        let reports: Vec<ReportEntry> = results.iter().map(|(_, _, report)| report).cloned().collect();
+        write_report(&reports, report_path)?;
     }
+    let topology_results = TopologyResults::new(
+        results
+            .into_iter()
+            .map(|(rule_name, result, _)| (rule_name, result))
+            .collect(),
+    );
+    topology_results.export(output, args.epsg)?;
     Ok(())
 }
 
-fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<TopologyResult<f64>> {
+/// Deserializes a JSON (or, by extension, YAML) array of rule commands from `config` and runs
+/// them exactly like [interactive_mode] does, reusing the same parallel execution and combined
+/// [TopologyResults] export. This gives users a reproducible, version-controllable QA suite
+/// they can re-run in CI instead of retyping commands or driving the interactive prompt.
+fn run_config(args: TopologyCheckerArgs) -> anyhow::Result<()> {
+    let (config, output) = match &args.command {
+        Command::RunConfig { config, output } => (config.clone(), output.clone()),
+        _ => unreachable!(),
+    };
+    let contents = std::fs::read_to_string(&config)
+        .with_context(|| format!("Failed to read config file {config:?}."))?;
+    let commands: Vec<Command> = match config.extension().and_then(|extension| extension.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {config:?} as YAML."))?,
+        _ => serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {config:?} as JSON."))?,
+    };
+    execute_commands(&args, commands, &output)
+}
+
+fn parse_rules(
+    args: TopologyCheckerArgs,
+    summarize: bool,
+) -> anyhow::Result<(TopologyResult<f64>, ReportEntry)> {
     let rule_name = rule_name(&args.command)?;
     let options = LayerOptions {
         name: &rule_name.clone(),
@@ -487,19 +824,32 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
     let mut config = ExportConfig {
         rule_name: rule_name.clone(),
         options: options,
+        format: args.output_format.into(),
         ..Default::default()
     };
+    let mut report = ReportEntry {
+        rule: rule_name.clone(),
+        input: PathBuf::new(),
+        srs: None,
+        total_features: 0,
+        violations: 0,
+        output: None,
+    };
     let result = match args.command {
         Command::Point(ref command) => match &command.command {
             PointRules::MustNotOverlap { points, overlaps } => {
-                let mut vector_dataset = VectorDataset::new(&points, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&points, &args)?;
+                report.input = points.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let points = flatten_points(vector_dataset.to_geo()?);
+                let points = filter_by_extent(points, args.extent);
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let result = points.must_not_overlap();
                 if overlaps.is_some() && !result.is_valid() {
                     config.output = overlaps.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_point().export(config)?
+                    clip_error_to_extent(result.unwrap_err_point(), args.extent).export(config)?
                 }
                 result
             }
@@ -508,17 +858,21 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 other,
                 overlaps,
             } => {
-                let mut vector_dataset = VectorDataset::new(&points, args.use_gdal)?;
-                let mut other = VectorDataset::new(&other, args.use_gdal)?;
-                validate_srs(&vector_dataset, &other)?;
-                let other = flatten_points(other.to_geo()?);
+                let mut vector_dataset = open_dataset_filtered(&points, &args)?;
+                let mut other = open_dataset_filtered(&other, &args)?;
+                report.input = points.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let other_geo = harmonize_srs(&vector_dataset, &other, other.to_geo()?, args.srs_mode.into())?;
+                let other = flatten_points(other_geo);
                 let points = flatten_points(vector_dataset.to_geo()?);
+                let points = filter_by_extent(points, args.extent);
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let result = points.must_not_overlap_with(other);
                 if overlaps.is_some() && !result.is_valid() {
                     config.output = overlaps.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_point().export(config)?
+                    clip_error_to_extent(result.unwrap_err_point(), args.extent).export(config)?
                 }
                 result
             }
@@ -527,34 +881,66 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 polygons,
                 outside,
             } => {
-                let mut vector_dataset = VectorDataset::new(&points, args.use_gdal)?;
-                let mut other = VectorDataset::new(&polygons, args.use_gdal)?;
-                validate_srs(&vector_dataset, &other)?;
-                let other = other.to_geo()?;
-                let other = flatten_polygons(other);
+                let mut vector_dataset = open_dataset_filtered(&points, &args)?;
+                let mut other = open_dataset_filtered(&polygons, &args)?;
+                report.input = points.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let other_geo = harmonize_srs(&vector_dataset, &other, other.to_geo()?, args.srs_mode.into())?;
+                let other = flatten_polygons(other_geo);
                 let geometries = vector_dataset.to_geo()?;
                 let points = flatten_points(geometries);
+                let points = filter_by_extent(points, args.extent);
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let result = points.must_be_inside(other);
                 if outside.is_some() && !result.is_valid() {
                     config.output = outside.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_point().export(config)?
+                    clip_error_to_extent(result.unwrap_err_point(), args.extent).export(config)?
+                }
+                result
+            }
+            PointRules::MustNotHaveDuplicates {
+                points,
+                duplicates,
+                tolerance,
+            } => {
+                let mut vector_dataset = open_dataset_filtered(&points, &args)?;
+                report.input = points.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let points = vector_dataset.to_geo()?;
+                let points = flatten_points(points);
+                let points = filter_by_extent(points, args.extent);
+                let result = points.must_not_have_duplicates_within(tolerance);
+                if duplicates.is_some() && !result.is_valid() {
+                    config.output = duplicates.as_ref();
+                    config.options.srs = srs.as_ref();
+                    clip_error_to_extent(result.unwrap_err_point(), args.extent).export(config)?;
                 }
                 result
             }
         },
         Command::Line(command) => match command.command {
             LineRules::MustNotHaveDangles { lines, dangles } => {
-                let mut vector_dataset = VectorDataset::new(&lines, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let lines = vector_dataset.to_geo()?;
-                let lines = flatten_linestrings(lines);
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
                 let result = lines.must_not_have_dangles();
                 if dangles.is_some() && !result.is_valid() {
                     config.output = dangles.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_point().export(config)?;
+                    clip_error_to_extent(result.unwrap_err_point(), args.extent).export(config)?;
                 };
                 result
             }
@@ -563,16 +949,25 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 single_points,
                 collinear_lines,
             } => {
-                let mut vector_dataset = VectorDataset::new(&lines, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let lines = vector_dataset.to_geo()?;
-                let lines = flatten_linestrings(lines);
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
                 let result = lines.must_not_intersect();
                 // Some workaround for the case where the rule can have
                 // two output files.
                 if (single_points.is_some() | collinear_lines.is_some()) && !result.is_valid() {
                     config.options.srs = srs.as_ref();
                     for error in result.unwrap_err() {
+                        let error = clip_error_to_extent(error, args.extent);
                         if let TopologyError::Point(_) = error {
                             if let Some(ref single_points) = single_points {
                                 let mut config = config.clone();
@@ -592,15 +987,23 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 result
             }
             LineRules::MustNotOverlap { lines, overlaps } => {
-                let mut vector_dataset = VectorDataset::new(&lines, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let lines = vector_dataset.to_geo()?;
-                let lines = flatten_linestrings(lines);
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
                 let result = lines.must_not_overlap();
                 if overlaps.is_some() && !result.is_valid() {
                     config.options.srs = srs.as_ref();
                     config.output = overlaps.as_ref();
-                    result.unwrap_err_linestring().export(config)?
+                    clip_error_to_extent(result.unwrap_err_linestring(), args.extent).export(config)?
                 }
                 result
             }
@@ -608,32 +1011,56 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 lines,
                 other,
                 overlaps,
+                tolerance,
             } => {
-                let mut vector_dataset = VectorDataset::new(&lines, args.use_gdal)?;
-                let mut other = VectorDataset::new(&other, args.use_gdal)?;
-                validate_srs(&vector_dataset, &other)?;
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                let mut other = open_dataset_filtered(&other, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let other_geo = harmonize_srs(&vector_dataset, &other, other.to_geo()?, args.srs_mode.into())?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let lines = vector_dataset.to_geo()?;
-                let lines = flatten_linestrings(lines);
-                let other = flatten_linestrings(other.to_geo()?);
-                let result = lines.must_not_overlap_with(other);
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let other = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(other_geo)?
+                } else {
+                    other_geo
+                });
+                let lines = filter_by_extent(lines, args.extent);
+                let result = match tolerance {
+                    Some(tolerance) => lines.must_not_overlap_with_tolerance(other, tolerance),
+                    None => lines.must_not_overlap_with(other),
+                };
                 if overlaps.is_some() && !result.is_valid() {
                     config.options.srs = srs.as_ref();
                     config.output = overlaps.as_ref();
-                    result.unwrap_err_linestring().export(config)?;
+                    clip_error_to_extent(result.unwrap_err_linestring(), args.extent).export(config)?;
                 }
                 result
             }
             LineRules::MustNotSelfOverlap { lines, overlaps } => {
-                let mut vector_dataset = VectorDataset::new(&lines, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let lines = vector_dataset.to_geo()?;
-                let lines = flatten_linestrings(lines);
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
                 let result = lines.must_not_self_overlap();
                 if overlaps.is_some() && !result.is_valid() {
                     config.options.srs = srs.as_ref();
                     config.output = overlaps.as_ref();
-                    result.unwrap_err_linestring().export(config)?
+                    clip_error_to_extent(result.unwrap_err_linestring(), args.extent).export(config)?
                 }
                 result
             }
@@ -642,33 +1069,119 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 polygons,
                 outside_lines,
             } => {
-                let mut vector_dataset = VectorDataset::new(&lines, args.use_gdal)?;
-                let mut other = VectorDataset::new(&polygons, args.use_gdal)?;
-                validate_srs(&vector_dataset, &other)?;
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                let mut other = open_dataset_filtered(&polygons, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let other_geo = harmonize_srs(&vector_dataset, &other, other.to_geo()?, args.srs_mode.into())?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let lines = vector_dataset.to_geo()?;
-                let lines = flatten_linestrings(lines);
-                let polygons = flatten_polygons(other.to_geo()?);
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
+                let polygons = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(other_geo)?
+                } else {
+                    other_geo
+                });
                 let result = lines.must_be_inside(polygons);
                 if outside_lines.is_some() && !result.is_valid() {
                     config.options.srs = srs.as_ref();
                     config.output = outside_lines.as_ref();
-                    result.unwrap_err_linestring().export(config)?;
+                    clip_error_to_extent(result.unwrap_err_linestring(), args.extent).export(config)?;
+                }
+                result
+            }
+            LineRules::MustNotHaveZeroLength {
+                lines,
+                vertices,
+                zero_length,
+                tolerance,
+            } => {
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let lines = vector_dataset.to_geo()?;
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
+                let result = lines.must_not_have_zero_length(tolerance);
+                if (vertices.is_some() | zero_length.is_some()) && !result.is_valid() {
+                    config.options.srs = srs.as_ref();
+                    for error in result.unwrap_err() {
+                        let error = clip_error_to_extent(error, args.extent);
+                        if let TopologyError::Point(_) = error {
+                            if let Some(ref vertices) = vertices {
+                                let mut config = config.clone();
+                                config.output = Some(vertices);
+                                error.export(config)?
+                            }
+                        }
+                        if let TopologyError::LineString(_) = error {
+                            if let Some(ref zero_length) = zero_length {
+                                let mut config = config.clone();
+                                config.output = Some(zero_length);
+                                error.export(config)?
+                            }
+                        }
+                    }
+                }
+                result
+            }
+            LineRules::MustNotHaveDuplicates {
+                lines,
+                duplicates,
+                tolerance,
+            } => {
+                let mut vector_dataset = open_dataset_filtered(&lines, &args)?;
+                report.input = lines.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let lines = vector_dataset.to_geo()?;
+                let lines = flatten_linestrings(if args.fix_invalid {
+                    fix_invalid(lines)?
+                } else {
+                    lines
+                });
+                let lines = filter_by_extent(lines, args.extent);
+                let result = lines.must_not_have_duplicates_within(tolerance);
+                if duplicates.is_some() && !result.is_valid() {
+                    config.output = duplicates.as_ref();
+                    config.options.srs = srs.as_ref();
+                    clip_error_to_extent(result.unwrap_err_linestring(), args.extent).export(config)?;
                 }
                 result
             }
         },
         Command::Polygon(command) => match command.command {
             PolygonRules::MustNotOverlap { polygons, overlaps } => {
-                let mut vector_dataset = VectorDataset::new(&polygons, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&polygons, &args)?;
+                report.input = polygons.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let polygons = vector_dataset.to_geo()?;
-                let polygons = flatten_polygons(polygons);
+                let polygons = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(polygons)?
+                } else {
+                    polygons
+                });
+                let polygons = filter_by_extent(polygons, args.extent);
                 let result = polygons.must_not_overlap();
                 if overlaps.is_some() && !result.is_valid() {
                     config.output = overlaps.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_polygon().export(config)?;
+                    clip_error_to_extent(result.unwrap_err_polygon(), args.extent).export(config)?;
                 }
                 result
             }
@@ -677,30 +1190,122 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 other,
                 overlaps,
             } => {
-                let mut vector_dataset = VectorDataset::new(&polygons, args.use_gdal)?;
-                let mut other = VectorDataset::new(&other, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&polygons, &args)?;
+                let mut other = open_dataset_filtered(&other, &args)?;
+                report.input = polygons.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let polygons = vector_dataset.to_geo()?;
-                let polygons = flatten_polygons(polygons);
-                let other = flatten_polygons(other.to_geo()?);
+                let polygons = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(polygons)?
+                } else {
+                    polygons
+                });
+                let other_geo = other.to_geo()?;
+                let other = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(other_geo)?
+                } else {
+                    other_geo
+                });
+                let polygons = filter_by_extent(polygons, args.extent);
                 let result = polygons.must_not_overlap_with(other);
                 if overlaps.is_some() {
                     config.output = overlaps.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_polygon().export(config)?;
+                    clip_error_to_extent(result.unwrap_err_polygon(), args.extent).export(config)?;
                 }
                 result
             }
             PolygonRules::MustNotHaveGaps { polygons, gaps } => {
-                let mut vector_dataset = VectorDataset::new(&polygons, args.use_gdal)?;
+                let mut vector_dataset = open_dataset_filtered(&polygons, &args)?;
+                report.input = polygons.clone();
+                report.total_features = vector_dataset.feature_count()?;
                 let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
                 let polygons = vector_dataset.to_geo()?;
-                let polygons = flatten_polygons(polygons);
+                let polygons = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(polygons)?
+                } else {
+                    polygons
+                });
+                let polygons = filter_by_extent(polygons, args.extent);
                 let result = polygons.must_not_have_gaps();
                 if gaps.is_some() {
                     config.output = gaps.as_ref();
                     config.options.srs = srs.as_ref();
-                    result.unwrap_err_linestring().export(config)?;
+                    clip_error_to_extent(result.unwrap_err_linestring(), args.extent).export(config)?;
+                }
+                result
+            }
+            PolygonRules::MustNotHaveZeroLength {
+                polygons,
+                slivers,
+                tolerance,
+            } => {
+                let mut vector_dataset = open_dataset_filtered(&polygons, &args)?;
+                report.input = polygons.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let polygons = vector_dataset.to_geo()?;
+                let polygons = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(polygons)?
+                } else {
+                    polygons
+                });
+                let polygons = filter_by_extent(polygons, args.extent);
+                let result = polygons.must_not_have_zero_length(tolerance);
+                if slivers.is_some() && !result.is_valid() {
+                    config.output = slivers.as_ref();
+                    config.options.srs = srs.as_ref();
+                    clip_error_to_extent(result.unwrap_err_polygon(), args.extent).export(config)?;
+                }
+                result
+            }
+            PolygonRules::MustNotHaveDuplicates {
+                polygons,
+                duplicates,
+                tolerance,
+            } => {
+                let mut vector_dataset = open_dataset_filtered(&polygons, &args)?;
+                report.input = polygons.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let polygons = vector_dataset.to_geo()?;
+                let polygons = flatten_polygons(if args.fix_invalid {
+                    fix_invalid(polygons)?
+                } else {
+                    polygons
+                });
+                let polygons = filter_by_extent(polygons, args.extent);
+                let result = polygons.must_not_have_duplicates_within(tolerance);
+                if duplicates.is_some() && !result.is_valid() {
+                    config.output = duplicates.as_ref();
+                    config.options.srs = srs.as_ref();
+                    clip_error_to_extent(result.unwrap_err_polygon(), args.extent).export(config)?;
+                }
+                result
+            }
+            PolygonRules::MustNotOverlapWithinMultipolygon { polygons, overlaps } => {
+                let mut vector_dataset = open_dataset_filtered(&polygons, &args)?;
+                report.input = polygons.clone();
+                report.total_features = vector_dataset.feature_count()?;
+                let srs = vector_dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let geometries = vector_dataset.to_geo()?;
+                let geometries = if args.fix_invalid {
+                    fix_invalid(geometries)?
+                } else {
+                    geometries
+                };
+                let polygons = PartitionedPolygons::from_geometries(geometries);
+                let result = polygons.must_not_overlap_within_multipolygon();
+                if overlaps.is_some() && !result.is_valid() {
+                    config.output = overlaps.as_ref();
+                    config.options.srs = srs.as_ref();
+                    clip_error_to_extent(result.unwrap_err_multipolygon(), args.extent).export(config)?;
                 }
                 result
             }
@@ -710,28 +1315,100 @@ fn parse_rules(args: TopologyCheckerArgs, summarize: bool) -> anyhow::Result<Top
                 geometries,
                 multiparts,
             } => {
-                let mut dataset = VectorDataset::new(&geometries, args.use_gdal)?;
+                let mut dataset = open_dataset_filtered(&geometries, &args)?;
+                report.input = geometries.clone();
+                report.total_features = dataset.feature_count()?;
                 let srs = dataset.srs()?;
-                let geometry = dataset.to_geo()?;
-                let result = geometry.must_not_be_multipart();
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let result = if args.streaming {
+                    let mut multipoints = Vec::new();
+                    let mut multilinestrings = Vec::new();
+                    let mut multipolygons = Vec::new();
+                    dataset.for_each_geometry(|geometry| {
+                        match geometry {
+                            Geometry::MultiPoint(multipoint) => multipoints.push(multipoint),
+                            Geometry::MultiLineString(multilinestring) => {
+                                multilinestrings.push(multilinestring)
+                            }
+                            Geometry::MultiPolygon(multipolygon) => {
+                                multipolygons.push(multipolygon)
+                            }
+                            _ => (),
+                        }
+                        Ok(())
+                    })?;
+                    let mut geometry_errors = Vec::new();
+                    if !multipoints.is_empty() {
+                        geometry_errors.push(TopologyError::MultiPoint(multipoints));
+                    }
+                    if !multilinestrings.is_empty() {
+                        geometry_errors.push(TopologyError::MultiLineString(multilinestrings));
+                    }
+                    if !multipolygons.is_empty() {
+                        geometry_errors.push(TopologyError::MultiPolygon(multipolygons));
+                    }
+                    if geometry_errors.is_empty() {
+                        TopologyResult::Valid
+                    } else {
+                        TopologyResult::Errors(geometry_errors)
+                    }
+                } else {
+                    let geometry = dataset.to_geo()?;
+                    geometry.must_not_be_multipart()
+                };
                 if multiparts.is_some() {
                     config.options.srs = srs.as_ref();
                     config.output = multiparts.as_ref();
                     for error in result.unwrap_err() {
-                        error.export(config.clone())?;
+                        clip_error_to_extent(error, args.extent).export(config.clone())?;
+                    }
+                }
+                result
+            }
+            GeometryRules::MustRelate {
+                geometries,
+                other,
+                pattern,
+                non_matching,
+            } => {
+                let mut dataset = open_dataset_filtered(&geometries, &args)?;
+                let mut other = open_dataset_filtered(&other, &args)?;
+                report.input = geometries.clone();
+                report.total_features = dataset.feature_count()?;
+                let other =
+                    harmonize_srs(&dataset, &other, other.to_geo()?, args.srs_mode.into())?;
+                let srs = dataset.srs()?;
+                report.srs = srs.as_ref().and_then(|srs| srs.authority().ok());
+                let geometries = dataset.to_geo()?;
+                let geometries = filter_by_extent(geometries, args.extent);
+                let result = geometries.must_relate(other, &pattern);
+                if non_matching.is_some() {
+                    config.options.srs = srs.as_ref();
+                    config.output = non_matching.as_ref();
+                    for error in result.unwrap_err() {
+                        clip_error_to_extent(error, args.extent).export(config.clone())?;
                     }
                 }
                 result
             }
         },
-        Command::GdalDrivers(_) | Command::Interactive { .. } | Command::Utilities(_) => {
+        Command::GdalDrivers(_)
+        | Command::Interactive { .. }
+        | Command::RunConfig { .. }
+        | Command::Utilities(_) => {
             unreachable!()
         }
     };
     if summarize {
         result.summary(Some(rule_name));
     }
-    Ok(result)
+    report.violations = if result.is_valid() {
+        0
+    } else {
+        result.unwrap_err().iter().map(TopologyError::len).sum()
+    };
+    report.output = config.output.cloned();
+    Ok((result, report))
 }
 
 fn parse_utils(args: TopologyCheckerArgs) -> anyhow::Result<()> {
@@ -740,6 +1417,7 @@ fn parse_utils(args: TopologyCheckerArgs) -> anyhow::Result<()> {
         | Command::Line(_)
         | Command::Point(_)
         | Command::Interactive { .. }
+        | Command::RunConfig { .. }
         | Command::Polygon(_) => {
             unreachable!()
         }
@@ -762,29 +1440,60 @@ fn parse_utils(args: TopologyCheckerArgs) -> anyhow::Result<()> {
         },
         Command::Utilities(command) => match command.command {
             Utilities::ExplodeLinestrings { linestrings, lines } => {
-                let mut dataset = VectorDataset::new(&linestrings, args.use_gdal)?;
-                let geometry = dataset.to_geo()?;
-                let linestrings = flatten_linestrings(geometry);
-                let exploded = explode_linestrings(&linestrings);
-                geometries_to_file(
-                    exploded
-                        .into_iter()
-                        .map(|line| line.to_gdal().expect("Failed to convert to GDAL."))
-                        .collect(),
-                    &lines,
-                    args.gdal_driver,
-                    Some(LayerOptions {
+                let mut dataset = open_dataset_filtered(&linestrings, &args)?;
+                let srs = dataset.srs()?;
+                if args.streaming {
+                    // Read features one at a time and write each one's exploded pieces straight
+                    // to the output layer, rather than collecting a `Vec` and exporting at the
+                    // end (the whole point of `--streaming`) or reopening the output dataset
+                    // once per input feature (which is what the non-incremental fix regressed
+                    // into before this).
+                    use gdal::vector::LayerAccess;
+
+                    let mut out_dataset = create_dataset(&lines, args.gdal_driver.clone())?;
+                    let mut out_layer = out_dataset.create_layer(LayerOptions {
                         name: "merged_linestrings",
-                        srs: dataset.srs()?.as_ref(),
+                        srs: srs.as_ref(),
                         ..Default::default()
-                    }),
-                )
+                    })?;
+                    dataset.for_each_geometry(|geometry: Geometry<f64>| {
+                        if let Geometry::LineString(linestring) = geometry {
+                            for piece in explode_linestrings(&vec![linestring]) {
+                                out_layer
+                                    .create_feature(
+                                        piece.to_gdal().expect("Failed to convert to GDAL."),
+                                    )
+                                    .with_context(|| "Failed to write an exploded linestring.")?;
+                            }
+                        }
+                        Ok(())
+                    })?;
+                } else {
+                    let geometry = dataset.to_geo()?;
+                    let linestrings = flatten_linestrings(geometry);
+                    let exploded = explode_linestrings(&linestrings);
+                    geometries_to_file(
+                        exploded
+                            .into_iter()
+                            .map(|line| line.to_gdal().expect("Failed to convert to GDAL."))
+                            .collect(),
+                        &lines,
+                        args.gdal_driver,
+                        Some(LayerOptions {
+                            name: "merged_linestrings",
+                            srs: srs.as_ref(),
+                            ..Default::default()
+                        }),
+                        None,
+                        false,
+                    )?
+                }
             }
             Utilities::MergeLinestrings {
                 linestrings,
                 merged,
             } => {
-                let mut dataset = VectorDataset::new(&linestrings, args.use_gdal)?;
+                let mut dataset = open_dataset_filtered(&linestrings, &args)?;
                 let geometry = dataset.to_geo()?;
                 let linestrings = flatten_linestrings(geometry);
                 let merged_linestrings = merge_linestrings(linestrings);
@@ -800,7 +1509,56 @@ fn parse_utils(args: TopologyCheckerArgs) -> anyhow::Result<()> {
                         srs: dataset.srs()?.as_ref(),
                         ..Default::default()
                     }),
-                )
+                    None,
+                    false,
+                )?
+            }
+            Utilities::MakeValid { geometries, valid } => {
+                let mut dataset = open_dataset_filtered(&geometries, &args)?;
+                let geometry = fix_invalid(dataset.to_geo()?)?;
+                let (name, repaired): (&str, Vec<gdal::vector::Geometry>) =
+                    if geometry.iter().any(is_polygon) {
+                        (
+                            "valid_polygons",
+                            flatten_polygons(geometry)
+                                .into_iter()
+                                .map(|polygon| polygon.to_gdal().expect("Failed to convert to GDAL."))
+                                .collect(),
+                        )
+                    } else {
+                        (
+                            "valid_linestrings",
+                            flatten_linestrings(geometry)
+                                .into_iter()
+                                .map(|line| line.to_gdal().expect("Failed to convert to GDAL."))
+                                .collect(),
+                        )
+                    };
+                geometries_to_file(
+                    repaired,
+                    &valid,
+                    args.gdal_driver,
+                    Some(LayerOptions {
+                        name,
+                        srs: dataset.srs()?.as_ref(),
+                        ..Default::default()
+                    }),
+                    None,
+                    false,
+                )?
+            }
+            Utilities::BuildNetworkGraph {
+                lines,
+                dot,
+                directed,
+                tolerance,
+            } => {
+                let mut dataset = open_dataset_filtered(&lines, &args)?;
+                let geometry = dataset.to_geo()?;
+                let linestrings = flatten_linestrings(geometry);
+                let graph = NetworkGraph::build(&linestrings, tolerance);
+                std::fs::write(&dot, graph.to_dot(directed))
+                    .with_context(|| format!("Failed to write the graph to {dot:?}."))?;
             }
         },
     }
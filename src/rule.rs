@@ -1,13 +1,23 @@
 mod must_be_inside;
 mod must_not_be_multipart;
 mod must_not_have_dangles;
+mod must_not_have_duplicates;
+mod must_not_have_zero_length;
 mod must_not_intersect;
 mod must_not_overlap;
+mod must_not_overlap_within_multipolygon;
 mod must_not_have_gaps;
+mod must_relate;
 
 pub use must_be_inside::MustBeInside;
 pub use must_not_be_multipart::MustNotBeMultipart;
 pub use must_not_have_dangles::MustNotHaveDangles;
+pub use must_not_have_duplicates::MustNotHaveDuplicates;
+pub use must_not_have_zero_length::MustNotHaveZeroLength;
 pub use must_not_intersect::MustNotIntersect;
-pub use must_not_overlap::{MustNotOverlap, MustNotSelfOverlap};
-pub use must_not_have_gaps::MustNotHaveGaps;
\ No newline at end of file
+pub use must_not_overlap::{
+    MustNotOverlap, MustNotOverlapTiled, MustNotOverlapWithTolerance, MustNotSelfOverlap,
+};
+pub use must_not_overlap_within_multipolygon::MustNotOverlapWithinMultipolygon;
+pub use must_not_have_gaps::MustNotHaveGaps;
+pub use must_relate::MustRelate;
\ No newline at end of file
@@ -1,13 +1,32 @@
 use crate::{TopologyError, TopologyResult};
-use geo::{Contains, GeoFloat, LineString, Point, Polygon};
+use geo::{GeoFloat, LineString, Point, Polygon, PreparedGeometry, Relate};
 use rayon::iter::ParallelBridge;
 use rayon::iter::ParallelIterator;
 use rstar::RTree;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ptr::addr_of;
 
 pub trait MustBeInside<T: GeoFloat + Send + Sync> {
+    /// Reports every element of `self` that doesn't fall inside one of `other`'s polygons.
+    /// Each target polygon is prepared with [`PreparedGeometry`] once and reused for every
+    /// candidate instead of re-walking its rings on every `contains` check, which pays off when
+    /// many points/linestrings are checked against few, large polygons.
     fn must_be_inside(self, other: Vec<Polygon<T>>) -> TopologyResult<T>;
 }
 
+/// Builds a [`PreparedGeometry`] for every polygon in `tree`, keyed by the polygon's address, so
+/// a polygon that shows up in many RTree candidate pairs only pays the cost of preparing its
+/// edge/relate index once rather than once per pair. Mirrors `must_not_overlap.rs`'s
+/// `prepare_polygons`.
+fn prepare_polygons<T: GeoFloat>(
+    tree: &RTree<Polygon<T>>,
+) -> HashMap<*const Polygon<T>, PreparedGeometry<'_, Polygon<T>>> {
+    tree.iter()
+        .map(|polygon| (addr_of!(*polygon), PreparedGeometry::from(polygon)))
+        .collect()
+}
+
 // TODO for both point and linestring implementations:
 // try to eliminate the clone in Some(*point) and Some(linestring.clone())
 
@@ -15,21 +34,30 @@ impl<T: GeoFloat + Send + Sync> MustBeInside<T> for Vec<Point<T>> {
     fn must_be_inside(self, other: Vec<Polygon<T>>) -> TopologyResult<T> {
         let points = RTree::bulk_load(self);
         let polygons: RTree<Polygon<T>> = RTree::bulk_load(other.into_iter().collect());
-        let inside_points: Vec<Point<T>> = points
+        let prepared = prepare_polygons(&polygons);
+        // Track matches by RTree node address rather than geometry value (the same
+        // `addr_of!`/`HashSet<*const _>` pattern `must_relate.rs` uses): a point with a NaN
+        // coordinate is never equal to itself under `PartialEq`, so a value-based lookup would
+        // report it as outside even after the check below found it inside.
+        let inside_points: HashSet<*const Point<T>> = points
             .intersection_candidates_with_other_tree(&polygons)
             .par_bridge()
             .filter_map(|(point, polygon)| {
-                if polygon.contains(point) {
-                    Some(*point)
+                let prepared_polygon = prepared
+                    .get(&addr_of!(*polygon))
+                    .expect("Every candidate polygon was prepared up front.");
+                if prepared_polygon.relate(point).is_contains() {
+                    Some(addr_of!(*point))
                 } else {
                     None
                 }
             })
             .collect();
         let outside_points: Vec<Point<T>> = points
-            .into_iter()
+            .iter()
             .par_bridge()
-            .filter(|point| !inside_points.contains(&point))
+            .filter(|point| !inside_points.contains(&addr_of!(**point)))
+            .copied()
             .collect();
         if outside_points.is_empty() {
             TopologyResult::Valid
@@ -42,22 +70,29 @@ impl<T: GeoFloat + Send + Sync> MustBeInside<T> for Vec<Point<T>> {
 impl<T: GeoFloat + Send + Sync> MustBeInside<T> for Vec<LineString<T>> {
     fn must_be_inside(self, other: Vec<Polygon<T>>) -> TopologyResult<T> {
         let linestrings = RTree::bulk_load(self);
-        let polygons = RTree::bulk_load(other.into_iter().collect());
-        let inside_linestrings: Vec<LineString<T>> = linestrings
+        let polygons: RTree<Polygon<T>> = RTree::bulk_load(other.into_iter().collect());
+        let prepared = prepare_polygons(&polygons);
+        // Track matches by RTree node address rather than geometry value, for the same reason
+        // as the `Vec<Point<T>>` impl above.
+        let inside_linestrings: HashSet<*const LineString<T>> = linestrings
             .intersection_candidates_with_other_tree(&polygons)
             .par_bridge()
             .filter_map(|(linestring, polygon)| {
-                if polygon.contains(linestring) {
-                    Some(linestring.clone())
+                let prepared_polygon = prepared
+                    .get(&addr_of!(*polygon))
+                    .expect("Every candidate polygon was prepared up front.");
+                if prepared_polygon.relate(linestring).is_contains() {
+                    Some(addr_of!(*linestring))
                 } else {
                     None
                 }
             })
             .collect();
         let outside_linestrings: Vec<LineString<T>> = linestrings
-            .into_iter()
+            .iter()
             .par_bridge()
-            .filter(|line| !inside_linestrings.contains(&line))
+            .filter(|line| !inside_linestrings.contains(&addr_of!(**line)))
+            .cloned()
             .collect();
         if outside_linestrings.is_empty() {
             TopologyResult::Valid
@@ -1,12 +1,21 @@
 use crate::{
-    util::{explode_linestrings, intersections, linestring_endpoints, sweep_points_to_points},
+    util::{
+        explode_linestrings, intersections, linestring_endpoints, snap_coord, snap_key,
+        sweep_points_to_points, SnapKey,
+    },
     TopologyError, TopologyResult,
 };
-use geo::{sweep::SweepPoint, GeoFloat, LineString};
+use geo::{sweep::SweepPoint, Coord, GeoFloat, LineString};
 use itertools::Itertools;
+use std::collections::HashMap;
 
 pub trait MustNotHaveDangles<T: GeoFloat> {
     fn must_not_have_dangles(&self) -> TopologyResult<T>;
+    /// Tolerance-aware variant of [`Self::must_not_have_dangles`]. Endpoints in the same or
+    /// adjacent cells of a grid with `tolerance`-wide cells are treated as coincident, which
+    /// covers every pair closer than `tolerance`. A non-positive `tolerance` falls back to the
+    /// exact-match path.
+    fn must_not_have_dangles_within(&self, tolerance: T) -> TopologyResult<T>;
 }
 
 impl<T: GeoFloat + Send + Sync> MustNotHaveDangles<T> for Vec<LineString<T>> {
@@ -34,4 +43,69 @@ impl<T: GeoFloat + Send + Sync> MustNotHaveDangles<T> for Vec<LineString<T>> {
             TopologyResult::Errors(geometry_errors)
         }
     }
+
+    fn must_not_have_dangles_within(&self, tolerance: T) -> TopologyResult<T> {
+        if tolerance <= T::zero() {
+            return self.must_not_have_dangles();
+        }
+        // Snap the flattened segments the same way the endpoints below are snapped, so the
+        // two sides of the membership check are apples-to-apples.
+        let snapped_lines: Vec<LineString<T>> = self
+            .iter()
+            .map(|linestring| {
+                LineString::from_iter(
+                    linestring
+                        .coords()
+                        .map(|coord| snap_coord(*coord, tolerance)),
+                )
+            })
+            .collect();
+        let (_, (_, improper)) = intersections::<T, SweepPoint<T>, SweepPoint<T>>(
+            explode_linestrings(&snapped_lines),
+        );
+
+        // Cluster the raw (unsnapped) endpoints by the grid cell they fall into. A cell
+        // touched by exactly one linestring end is a candidate dangle.
+        let mut clusters: HashMap<SnapKey, Vec<SweepPoint<T>>> = HashMap::new();
+        for endpoint in linestring_endpoints(self) {
+            let coord = Coord {
+                x: endpoint.x,
+                y: endpoint.y,
+            };
+            clusters
+                .entry(snap_key(coord, tolerance))
+                .or_default()
+                .push(endpoint);
+        }
+        let dangles = clusters
+            .iter()
+            .filter(|(key, cluster)| {
+                // Endpoints straddling a cell boundary land in adjacent cells, so check those too.
+                let (kx, ky) = **key;
+                cluster.len() == 1
+                    && !(kx - 1..=kx + 1).any(|x| {
+                        (ky - 1..=ky + 1).any(|y| (x, y) != (kx, ky) && clusters.contains_key(&(x, y)))
+                    })
+            })
+            .map(|(_, cluster)| cluster[0])
+            .filter(|endpoint| {
+                let snapped = snap_coord(
+                    Coord {
+                        x: endpoint.x,
+                        y: endpoint.y,
+                    },
+                    tolerance,
+                );
+                !improper.contains(&<Coord<T> as Into<SweepPoint<T>>>::into(snapped))
+            })
+            .collect_vec();
+        let geometry_errors = vec![TopologyError::Point(
+            sweep_points_to_points(dangles).into_iter().collect(),
+        )];
+        if geometry_errors.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(geometry_errors)
+        }
+    }
 }
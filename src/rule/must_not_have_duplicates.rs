@@ -0,0 +1,646 @@
+use crate::util::{snap_key, SnapKey};
+use crate::{TopologyError, TopologyResult};
+use geo::{BoundingRect, Coord, EuclideanDistance, GeoFloat, Line, LineString, LinesIter, Point, Polygon, Rect};
+use rstar::RTree;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ptr::addr_of;
+
+/// Reports duplicate and collinear-overlapping lines: the missing rule the commented-out
+/// `_dedup_linestrings` in [`crate::algorithm::merge_linestrings`] was always meant to replace.
+/// Mirrors QGIS' topology plugin `checkDuplicates` check, extended to points and polygons.
+pub trait MustNotHaveDuplicates<T: GeoFloat> {
+    /// Reports exact geometric duplicates: for lines, regardless of which end each copy starts
+    /// from (and, where supported, partial collinear overlaps); for polygons, regardless of ring
+    /// rotation/winding direction.
+    fn must_not_have_duplicates(self) -> TopologyResult<T>;
+    /// Tolerance-aware variant of [`Self::must_not_have_duplicates`]: two features are
+    /// considered duplicates when their bounding boxes are within `tolerance` of each other and
+    /// every corresponding vertex pair is within `tolerance` as well, rather than requiring exact
+    /// equality. A non-positive `tolerance` falls back to the exact-match path.
+    fn must_not_have_duplicates_within(self, tolerance: T) -> TopologyResult<T>;
+}
+
+/// Checks whether `a` and `b` are within `tolerance` of each other on every side, as a cheap
+/// pre-filter before comparing vertices one by one.
+fn bbox_close<T: GeoFloat>(a: Rect<T>, b: Rect<T>, tolerance: T) -> bool {
+    (a.min().x - b.min().x).abs() <= tolerance
+        && (a.min().y - b.min().y).abs() <= tolerance
+        && (a.max().x - b.max().x).abs() <= tolerance
+        && (a.max().y - b.max().y).abs() <= tolerance
+}
+
+fn coord_distance<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> T {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+fn coord_less<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> bool {
+    match a.x.partial_cmp(&b.x).expect("Encountered a NaN coordinate.") {
+        std::cmp::Ordering::Equal => a.y < b.y,
+        ordering => ordering == std::cmp::Ordering::Less,
+    }
+}
+
+fn sequence_less<T: GeoFloat>(a: &[Coord<T>], b: &[Coord<T>]) -> bool {
+    for (&ca, &cb) in a.iter().zip(b.iter()) {
+        if coord_less(ca, cb) {
+            return true;
+        }
+        if coord_less(cb, ca) {
+            return false;
+        }
+    }
+    a.len() < b.len()
+}
+
+fn rotate_to_min<T: GeoFloat>(coords: &[Coord<T>]) -> Vec<Coord<T>> {
+    if coords.is_empty() {
+        return Vec::new();
+    }
+    let mut min_index = 0;
+    for index in 1..coords.len() {
+        if coord_less(coords[index], coords[min_index]) {
+            min_index = index;
+        }
+    }
+    coords[min_index..]
+        .iter()
+        .chain(coords[..min_index].iter())
+        .copied()
+        .collect()
+}
+
+/// Canonicalizes a polygon ring so rotation (which vertex it starts at) and winding direction
+/// don't matter when comparing for duplicates: rotates to start at the lexicographically
+/// smallest vertex, then keeps whichever of the two winding directions sorts smaller.
+fn canonical_ring<T: GeoFloat>(ring: &LineString<T>) -> Vec<Coord<T>> {
+    let coords = &ring.0[..ring.0.len().saturating_sub(1)]; // drop the closing duplicate vertex
+    let forward = rotate_to_min(coords);
+    let reversed: Vec<Coord<T>> = coords.iter().rev().copied().collect();
+    let backward = rotate_to_min(&reversed);
+    if sequence_less(&backward, &forward) {
+        backward
+    } else {
+        forward
+    }
+}
+
+/// Canonicalizes a polygon's rings so it can be compared for duplicates regardless of ring
+/// rotation/direction or interior-ring order.
+fn canonical_polygon<T: GeoFloat>(polygon: &Polygon<T>) -> (Vec<Coord<T>>, Vec<Vec<Coord<T>>>) {
+    let exterior = canonical_ring(polygon.exterior());
+    let mut interiors: Vec<Vec<Coord<T>>> =
+        polygon.interiors().iter().map(canonical_ring).collect();
+    interiors.sort_by(|a, b| {
+        if sequence_less(a, b) {
+            std::cmp::Ordering::Less
+        } else if sequence_less(b, a) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+    (exterior, interiors)
+}
+
+/// Compares two canonicalized rings vertex-by-vertex, reporting them as matching only when they
+/// have the same vertex count and every corresponding pair is within `tolerance`.
+fn rings_close<T: GeoFloat>(a: &[Coord<T>], b: &[Coord<T>], tolerance: T) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .all(|(&ca, &cb)| coord_distance(ca, cb) <= tolerance)
+}
+
+fn cross2d<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> T {
+    a.x * b.y - a.y * b.x
+}
+
+/// Canonicalizes a linestring's coordinate sequence so direction doesn't matter when comparing
+/// for exact duplicates: whichever endpoint sorts lexicographically smaller becomes the start.
+fn canonical_coords<T: GeoFloat>(linestring: &LineString<T>) -> Vec<Coord<T>> {
+    let forward = linestring.0.clone();
+    let (first, last) = (forward[0], *forward.last().expect("Linestring has no coordinates."));
+    let first_is_smaller = match first.x.partial_cmp(&last.x).expect("Encountered a NaN coordinate.") {
+        std::cmp::Ordering::Equal => first.y <= last.y,
+        ordering => ordering == std::cmp::Ordering::Less,
+    };
+    if first_is_smaller {
+        forward
+    } else {
+        forward.into_iter().rev().collect()
+    }
+}
+
+/// Tests segments `a` and `b` for exact collinear overlap: both the cross product of their
+/// direction vectors and the cross product of the offset between their start points and `a`'s
+/// direction must be exactly zero. When collinear, returns the sub-segment of `a` that `b`
+/// overlaps, or `None` if they don't actually overlap (merely collinear, e.g. disjoint on the
+/// same infinite line). Mirrors [`super::must_not_overlap::MustNotOverlapWithTolerance`]'s
+/// `collinear_overlap`, but with the tolerance pinned to zero, since this rule is about exact
+/// redundant geometry rather than near-collinearity in digitized data.
+fn collinear_overlap<T: GeoFloat>(a: Line<T>, b: Line<T>) -> Option<Line<T>> {
+    let direction = Coord {
+        x: a.end.x - a.start.x,
+        y: a.end.y - a.start.y,
+    };
+    let length_squared = direction.x * direction.x + direction.y * direction.y;
+    if length_squared <= T::zero() {
+        return None;
+    }
+    let length = length_squared.sqrt();
+    let db = Coord {
+        x: b.end.x - b.start.x,
+        y: b.end.y - b.start.y,
+    };
+    if cross2d(direction, db) != T::zero() {
+        return None;
+    }
+    let offset = Coord {
+        x: b.start.x - a.start.x,
+        y: b.start.y - a.start.y,
+    };
+    if cross2d(direction, offset) != T::zero() {
+        return None;
+    }
+
+    let unit = Coord {
+        x: direction.x / length,
+        y: direction.y / length,
+    };
+    let project = |point: Coord<T>| -> T {
+        let offset = Coord {
+            x: point.x - a.start.x,
+            y: point.y - a.start.y,
+        };
+        offset.x * unit.x + offset.y * unit.y
+    };
+    let (b_start, b_end) = (project(b.start), project(b.end));
+    let (low, high) = (b_start.min(b_end).max(T::zero()), b_start.max(b_end).min(length));
+    if high <= low {
+        return None;
+    }
+    let point_at = |t: T| Coord {
+        x: a.start.x + unit.x * t,
+        y: a.start.y + unit.y * t,
+    };
+    Some(Line::new(point_at(low), point_at(high)))
+}
+
+/// Merges `segments` sharing an endpoint into contiguous [`LineString`]s, so adjacent
+/// overlapping sub-segments are reported as one feature rather than many tiny pieces.
+fn merge_adjacent_segments<T: GeoFloat>(segments: Vec<Line<T>>) -> Vec<LineString<T>> {
+    fn merge_two<T: GeoFloat>(a: &LineString<T>, b: &LineString<T>) -> Option<LineString<T>> {
+        let (a_start, a_end) = (a.0[0], a.0[a.0.len() - 1]);
+        let (b_start, b_end) = (b.0[0], b.0[b.0.len() - 1]);
+        if a_start == b_start {
+            Some(LineString::from_iter(
+                a.coords_iter().rev().chain(b.coords_iter().skip(1)),
+            ))
+        } else if a_end == b_start {
+            Some(LineString::from_iter(
+                a.coords_iter().chain(b.coords_iter().skip(1)),
+            ))
+        } else if a_end == b_end {
+            Some(LineString::from_iter(
+                a.coords_iter().chain(b.coords_iter().rev().skip(1)),
+            ))
+        } else if a_start == b_end {
+            Some(LineString::from_iter(
+                b.coords_iter().chain(a.coords_iter().skip(1)),
+            ))
+        } else {
+            None
+        }
+    }
+
+    use geo::CoordsIter;
+    let mut linestrings: Vec<LineString<T>> = segments
+        .into_iter()
+        .map(|line| LineString::from(vec![line.start, line.end]))
+        .collect();
+    loop {
+        let mut merged_at = None;
+        'outer: for i in 0..linestrings.len() {
+            for j in (i + 1)..linestrings.len() {
+                if let Some(merged) = merge_two(&linestrings[i], &linestrings[j]) {
+                    merged_at = Some((i, j, merged));
+                    break 'outer;
+                }
+            }
+        }
+        match merged_at {
+            Some((i, j, merged)) => {
+                linestrings[i] = merged;
+                linestrings.remove(j);
+            }
+            None => break,
+        }
+    }
+    linestrings
+}
+
+impl<T: GeoFloat + Send + Sync> MustNotHaveDuplicates<T> for Vec<LineString<T>> {
+    fn must_not_have_duplicates(self) -> TopologyResult<T> {
+        let tree = RTree::bulk_load(self);
+        // Same lightweight address-pair dedup as `Vec<Polygon<T>>::must_not_overlap`, since
+        // `intersection_candidates_with_other_tree` yields both (A, B) and (B, A).
+        let mut addresses = Vec::new();
+        // Tracks which lines already made it into `duplicates` by address, so a cluster of 3+
+        // mutual duplicates reports every member instead of only whichever side of each pair the
+        // RTree happened to yield first.
+        let mut reported: HashSet<*const LineString<T>> = HashSet::new();
+        let mut duplicates: Vec<LineString<T>> = Vec::new();
+        let mut overlap_segments: Vec<Line<T>> = Vec::new();
+
+        for (line, other) in tree.intersection_candidates_with_other_tree(&tree) {
+            if std::ptr::addr_eq(line, other) {
+                continue;
+            }
+            let address = (addr_of!(*line), addr_of!(*other));
+            if addresses.contains(&(address.1, address.0)) {
+                continue;
+            }
+            addresses.push(address);
+
+            if canonical_coords(line) == canonical_coords(other) {
+                if reported.insert(addr_of!(*line)) {
+                    duplicates.push(line.clone());
+                }
+                if reported.insert(addr_of!(*other)) {
+                    duplicates.push(other.clone());
+                }
+                continue;
+            }
+            for segment_a in line.lines_iter() {
+                for segment_b in other.lines_iter() {
+                    if let Some(overlap) = collinear_overlap(segment_a, segment_b) {
+                        overlap_segments.push(overlap);
+                    }
+                }
+            }
+        }
+
+        let mut geometry_errors = Vec::new();
+        if !duplicates.is_empty() {
+            geometry_errors.push(TopologyError::LineString(duplicates));
+        }
+        if !overlap_segments.is_empty() {
+            geometry_errors.push(TopologyError::LineString(merge_adjacent_segments(
+                overlap_segments,
+            )));
+        }
+        if geometry_errors.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(geometry_errors)
+        }
+    }
+
+    fn must_not_have_duplicates_within(self, tolerance: T) -> TopologyResult<T> {
+        if tolerance <= T::zero() {
+            return self.must_not_have_duplicates();
+        }
+        let tree = RTree::bulk_load(self);
+        let mut addresses = Vec::new();
+        let mut reported: HashSet<*const LineString<T>> = HashSet::new();
+        let mut duplicates: Vec<LineString<T>> = Vec::new();
+
+        for (line, other) in tree.intersection_candidates_with_other_tree(&tree) {
+            if std::ptr::addr_eq(line, other) {
+                continue;
+            }
+            let address = (addr_of!(*line), addr_of!(*other));
+            if addresses.contains(&(address.1, address.0)) {
+                continue;
+            }
+            addresses.push(address);
+
+            let (line_box, other_box) = match (line.bounding_rect(), other.bounding_rect()) {
+                (Some(line_box), Some(other_box)) => (line_box, other_box),
+                _ => continue,
+            };
+            if !bbox_close(line_box, other_box, tolerance) {
+                continue;
+            }
+            let forward_close = line.0.len() == other.0.len()
+                && line.0.iter().zip(other.0.iter()).all(|(&a, &b)| coord_distance(a, b) <= tolerance);
+            let backward_close = line.0.len() == other.0.len()
+                && line.0.iter().zip(other.0.iter().rev()).all(|(&a, &b)| coord_distance(a, b) <= tolerance);
+            if forward_close || backward_close {
+                if reported.insert(addr_of!(*line)) {
+                    duplicates.push(line.clone());
+                }
+                if reported.insert(addr_of!(*other)) {
+                    duplicates.push(other.clone());
+                }
+            }
+        }
+
+        if duplicates.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::LineString(duplicates)])
+        }
+    }
+}
+
+impl<T: GeoFloat + Send + Sync> MustNotHaveDuplicates<T> for Vec<Point<T>> {
+    fn must_not_have_duplicates(self) -> TopologyResult<T> {
+        let points = RTree::bulk_load(self);
+        // Same lightweight address-pair dedup as `Vec<Point<T>>::must_not_overlap`, since
+        // `intersection_candidates_with_other_tree` yields both (A, B) and (B, A).
+        let mut addresses = Vec::new();
+        // Tracks which points already made it into `duplicates` by address, so a cluster of 3+
+        // mutual duplicates reports every member instead of only whichever side of each pair the
+        // RTree happened to yield first.
+        let mut reported: HashSet<*const Point<T>> = HashSet::new();
+        let mut duplicates: Vec<Point<T>> = Vec::new();
+        for (point, other) in points.intersection_candidates_with_other_tree(&points) {
+            if std::ptr::addr_eq(point, other) {
+                continue;
+            }
+            let address = (addr_of!(*point), addr_of!(*other));
+            if addresses.contains(&(address.1, address.0)) {
+                continue;
+            }
+            addresses.push(address);
+            if point == other {
+                if reported.insert(addr_of!(*point)) {
+                    duplicates.push(*point);
+                }
+                if reported.insert(addr_of!(*other)) {
+                    duplicates.push(*other);
+                }
+            }
+        }
+        if duplicates.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::Point(duplicates)])
+        }
+    }
+
+    fn must_not_have_duplicates_within(self, tolerance: T) -> TopologyResult<T> {
+        if tolerance <= T::zero() {
+            return self.must_not_have_duplicates();
+        }
+        // `self`'s points have degenerate (zero-size) RTree envelopes, so an RTree self-join
+        // would miss near-duplicates that aren't exactly coincident. Bucket by snapped grid cell
+        // instead, the same QGIS-topology-plugin-style technique used for tolerance-aware
+        // endpoint matching elsewhere (see `must_not_intersect_within`).
+        let mut buckets: HashMap<SnapKey, Vec<usize>> = HashMap::new();
+        for (index, point) in self.iter().enumerate() {
+            buckets
+                .entry(snap_key(point.0, tolerance))
+                .or_default()
+                .push(index);
+        }
+
+        let mut reported = vec![false; self.len()];
+        let mut duplicates = Vec::new();
+        for (index, point) in self.iter().enumerate() {
+            if reported[index] {
+                continue;
+            }
+            let (kx, ky) = snap_key(point.0, tolerance);
+            let mut found = false;
+            'neighbors: for x in kx - 1..=kx + 1 {
+                for y in ky - 1..=ky + 1 {
+                    if let Some(candidates) = buckets.get(&(x, y)) {
+                        for &other_index in candidates {
+                            if other_index != index
+                                && point.euclidean_distance(&self[other_index]) <= tolerance
+                            {
+                                found = true;
+                                break 'neighbors;
+                            }
+                        }
+                    }
+                }
+            }
+            if found {
+                reported[index] = true;
+                duplicates.push(*point);
+            }
+        }
+
+        if duplicates.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::Point(duplicates)])
+        }
+    }
+}
+
+impl<T: GeoFloat + Send + Sync> MustNotHaveDuplicates<T> for Vec<Polygon<T>> {
+    fn must_not_have_duplicates(self) -> TopologyResult<T> {
+        let tree = RTree::bulk_load(self);
+        let mut addresses = Vec::new();
+        // Tracks which polygons already made it into `duplicates` by address, so a cluster of 3+
+        // mutual duplicates reports every member instead of only whichever side of each pair the
+        // RTree happened to yield first.
+        let mut reported: HashSet<*const Polygon<T>> = HashSet::new();
+        let mut duplicates: Vec<Polygon<T>> = Vec::new();
+
+        for (polygon, other) in tree.intersection_candidates_with_other_tree(&tree) {
+            if std::ptr::addr_eq(polygon, other) {
+                continue;
+            }
+            let address = (addr_of!(*polygon), addr_of!(*other));
+            if addresses.contains(&(address.1, address.0)) {
+                continue;
+            }
+            addresses.push(address);
+
+            if canonical_polygon(polygon) == canonical_polygon(other) {
+                if reported.insert(addr_of!(*polygon)) {
+                    duplicates.push(polygon.clone());
+                }
+                if reported.insert(addr_of!(*other)) {
+                    duplicates.push(other.clone());
+                }
+            }
+        }
+
+        if duplicates.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::Polygon(duplicates)])
+        }
+    }
+
+    fn must_not_have_duplicates_within(self, tolerance: T) -> TopologyResult<T> {
+        if tolerance <= T::zero() {
+            return self.must_not_have_duplicates();
+        }
+        let tree = RTree::bulk_load(self);
+        let mut addresses = Vec::new();
+        let mut reported: HashSet<*const Polygon<T>> = HashSet::new();
+        let mut duplicates: Vec<Polygon<T>> = Vec::new();
+
+        for (polygon, other) in tree.intersection_candidates_with_other_tree(&tree) {
+            if std::ptr::addr_eq(polygon, other) {
+                continue;
+            }
+            let address = (addr_of!(*polygon), addr_of!(*other));
+            if addresses.contains(&(address.1, address.0)) {
+                continue;
+            }
+            addresses.push(address);
+
+            let (polygon_box, other_box) = match (polygon.bounding_rect(), other.bounding_rect()) {
+                (Some(polygon_box), Some(other_box)) => (polygon_box, other_box),
+                _ => continue,
+            };
+            if !bbox_close(polygon_box, other_box, tolerance) {
+                continue;
+            }
+            let (exterior, interiors) = canonical_polygon(polygon);
+            let (other_exterior, other_interiors) = canonical_polygon(other);
+            let exteriors_close = rings_close(&exterior, &other_exterior, tolerance);
+            let interiors_close = interiors.len() == other_interiors.len()
+                && interiors
+                    .iter()
+                    .zip(other_interiors.iter())
+                    .all(|(a, b)| rings_close(a, b, tolerance));
+            if exteriors_close && interiors_close {
+                if reported.insert(addr_of!(*polygon)) {
+                    duplicates.push(polygon.clone());
+                }
+                if reported.insert(addr_of!(*other)) {
+                    duplicates.push(other.clone());
+                }
+            }
+        }
+
+        if duplicates.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::Polygon(duplicates)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{line_string, point, polygon};
+
+    #[test]
+    fn exact_duplicate_reversed() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 2., y: 2.), (x: 3., y: 1.)],
+            line_string![(x: 3., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)],
+        ];
+        let result = input.clone().must_not_have_duplicates();
+        let TopologyError::LineString(duplicates) = result.unwrap_err_linestring() else {
+            panic!("expected a LineString error");
+        };
+        // Both sides of the duplicate pair are reported, not just whichever one the RTree
+        // happened to yield first.
+        assert_eq!(duplicates.len(), 2);
+        for line in &input {
+            assert!(duplicates.contains(line));
+        }
+    }
+
+    #[test]
+    fn cluster_of_three_near_duplicates_all_reported() {
+        // A, B and C are pairwise within tolerance but no two are identical. Previously only
+        // whichever line happened to be the first element of each RTree-yielded pair was kept,
+        // so a genuine near-duplicate could be silently dropped from the report.
+        let a = line_string![(x: 0., y: 0.), (x: 2., y: 0.)];
+        let b = line_string![(x: 0.01, y: 0.), (x: 2.01, y: 0.)];
+        let c = line_string![(x: 0.02, y: 0.), (x: 2.02, y: 0.)];
+        let input = vec![a.clone(), b.clone(), c.clone()];
+        let result = input.must_not_have_duplicates_within(0.05);
+        let TopologyError::LineString(duplicates) = result.unwrap_err_linestring() else {
+            panic!("expected a LineString error");
+        };
+        assert!(duplicates.contains(&a));
+        assert!(duplicates.contains(&b));
+        assert!(duplicates.contains(&c));
+    }
+
+    #[test]
+    fn collinear_overlap_reported() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 4., y: 0.)],
+            line_string![(x: 2., y: 0.), (x: 6., y: 0.)],
+        ];
+        let output = vec![line_string![(x: 2., y: 0.), (x: 4., y: 0.)]];
+        assert_eq!(
+            input.must_not_have_duplicates().unwrap_err_linestring(),
+            &TopologyError::LineString(output)
+        );
+    }
+
+    #[test]
+    fn disjoint_is_valid() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 5., y: 5.), (x: 6., y: 5.)],
+        ];
+        assert!(input.must_not_have_duplicates().is_valid());
+    }
+
+    #[test]
+    fn near_duplicate_line_reported_within_tolerance() {
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 2., y: 0.)],
+            line_string![(x: 0.05, y: 0.), (x: 2.05, y: 0.)],
+        ];
+        assert!(!input.must_not_have_duplicates_within(0.1).is_valid());
+        let input = vec![
+            line_string![(x: 0., y: 0.), (x: 2., y: 0.)],
+            line_string![(x: 0.05, y: 0.), (x: 2.05, y: 0.)],
+        ];
+        assert!(input.must_not_have_duplicates_within(0.01).is_valid());
+    }
+
+    #[test]
+    fn coincident_points_are_reported() {
+        let input = vec![point!(x: 1., y: 1.), point!(x: 1., y: 1.), point!(x: 5., y: 5.)];
+        let TopologyError::Point(duplicates) = input.must_not_have_duplicates().unwrap_err_point()
+        else {
+            panic!("expected a Point error");
+        };
+        // Both coincident points are reported, not just one of the pair.
+        assert_eq!(duplicates, &vec![point!(x: 1., y: 1.), point!(x: 1., y: 1.)]);
+    }
+
+    #[test]
+    fn near_duplicate_point_reported_within_tolerance() {
+        let input = vec![point!(x: 1., y: 1.), point!(x: 1.05, y: 1.)];
+        assert!(!input.clone().must_not_have_duplicates_within(0.1).is_valid());
+        assert!(input.must_not_have_duplicates_within(0.01).is_valid());
+    }
+
+    #[test]
+    fn rotated_polygon_duplicate_is_reported() {
+        let input = vec![
+            polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.)],
+            polygon![(x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.), (x: 1., y: 0.)],
+        ];
+        let result = input.clone().must_not_have_duplicates();
+        let TopologyError::Polygon(duplicates) = result.unwrap_err_polygon() else {
+            panic!("expected a Polygon error");
+        };
+        // Both sides of the duplicate pair are reported, not just whichever one the RTree
+        // happened to yield first.
+        assert_eq!(duplicates.len(), 2);
+        for polygon in &input {
+            assert!(duplicates.contains(polygon));
+        }
+    }
+
+    #[test]
+    fn disjoint_polygons_are_valid() {
+        let input = vec![
+            polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.)],
+            polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 6.)],
+        ];
+        assert!(input.must_not_have_duplicates().is_valid());
+    }
+}
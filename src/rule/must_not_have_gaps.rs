@@ -1,10 +1,32 @@
-use crate::util::explode_linestrings;
+use crate::util::{explode_linestrings, snap_coord};
 use crate::{TopologyError, TopologyResult};
-use geo::{GeoFloat, Contains, LineString, Polygon};
+use geo::{Contains, GeoFloat, LineString, Polygon, PreparedGeometry, Relate};
 use rstar::{RTree, RTreeObject};
+use std::collections::HashMap;
+use std::ptr::addr_of;
 
 pub trait MustNotHaveGaps<T: GeoFloat> {
+    /// Every exploded boundary segment is prepared with [`PreparedGeometry`] once and reused
+    /// across every envelope-intersecting neighbor, instead of paying `geo::Contains`'s
+    /// predicate-state setup on every call. The two-match early-exit semantics (a segment shared
+    /// by exactly two boundaries is an interior edge, valid) are unchanged.
     fn must_not_have_gaps(self) -> TopologyResult<T>;
+    /// Tolerance-aware variant of [`Self::must_not_have_gaps`]. Boundary vertices closer than
+    /// `tolerance` are snapped onto a common grid before the containment sweep, so a gap
+    /// caused only by digitizing noise (rather than a genuine missing edge) isn't reported.
+    /// A non-positive `tolerance` falls back to the exact-match path.
+    fn must_not_have_gaps_within(self, tolerance: T) -> TopologyResult<T>;
+}
+
+/// Builds a [`PreparedGeometry`] for every exploded boundary segment in `tree`, keyed by the
+/// segment's address, so each segment pays the cost of preparing its relate index once and
+/// reuses it across all of its envelope-intersecting neighbors. Mirrors `must_not_overlap.rs`'s `prepare_polygons`.
+fn prepare_lines<T: GeoFloat>(
+    tree: &RTree<LineString<T>>,
+) -> HashMap<*const LineString<T>, PreparedGeometry<'_, LineString<T>>> {
+    tree.iter()
+        .map(|line| (addr_of!(*line), PreparedGeometry::from(line)))
+        .collect()
 }
 
 impl<T: GeoFloat + Send + Sync> MustNotHaveGaps<T> for Vec<Polygon<T>> {
@@ -22,6 +44,59 @@ impl<T: GeoFloat + Send + Sync> MustNotHaveGaps<T> for Vec<Polygon<T>> {
             .collect();
         let lines = explode_linestrings(&boundaries);
         let tree = RTree::bulk_load(lines);
+        let prepared = prepare_lines(&tree);
+        let results: Vec<LineString<T>> = tree
+            .iter()
+            .filter_map(|line| {
+                let prepared_line = prepared
+                    .get(&addr_of!(*line))
+                    .expect("Every boundary segment was prepared up front.");
+                let mut counter = 0;
+                for other in tree.locate_in_envelope_intersecting(&line.envelope()) {
+                    if prepared_line.relate(other).is_contains() {
+                        counter += 1
+                    }
+                    if counter == 2 {
+                        return None;
+                    }
+                }
+                Some(line.into())
+            })
+            .collect();
+        if results.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::LineString(results)])
+        }
+    }
+
+    fn must_not_have_gaps_within(self, tolerance: T) -> TopologyResult<T> {
+        if tolerance <= T::zero() {
+            return self.must_not_have_gaps();
+        }
+        let boundaries: Vec<LineString<T>> = self
+            .into_iter()
+            .flat_map(|polygon| {
+                polygon
+                    .interiors()
+                    .to_owned()
+                    .into_iter()
+                    .chain(std::iter::once(polygon.exterior().to_owned()))
+            })
+            .collect();
+        // Snap every boundary vertex onto the same grid before exploding, so coincident-but-
+        // not-quite-equal vertices contain each other exactly, the same way `line.contains`
+        // already does for truly coincident ones.
+        let snapped_boundaries: Vec<LineString<T>> = boundaries
+            .iter()
+            .map(|boundary| {
+                LineString::from_iter(
+                    boundary.coords().map(|coord| snap_coord(*coord, tolerance)),
+                )
+            })
+            .collect();
+        let lines = explode_linestrings(&snapped_boundaries);
+        let tree = RTree::bulk_load(lines);
         let results: Vec<LineString<T>> = tree
             .iter()
             .filter_map(|line| {
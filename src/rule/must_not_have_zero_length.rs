@@ -0,0 +1,114 @@
+use crate::{TopologyError, TopologyResult};
+use geo::{Area, EuclideanLength, GeoFloat, LineString, Point, Polygon};
+
+/// Flags degenerate geometry the other rules silently pass through, modeled on GRASS'
+/// `Vect_topo_check`: zero-length lines, consecutive duplicate vertices within a line, and
+/// polygons with zero or near-zero area ("slivers").
+pub trait MustNotHaveZeroLength<T: GeoFloat> {
+    /// `tolerance` is the threshold at or below which a line's length, or a polygon's area, is
+    /// considered zero, and at or below which two consecutive vertices are considered duplicates.
+    fn must_not_have_zero_length(self, tolerance: T) -> TopologyResult<T>;
+}
+
+impl<T: GeoFloat> MustNotHaveZeroLength<T> for Vec<LineString<T>> {
+    fn must_not_have_zero_length(self, tolerance: T) -> TopologyResult<T> {
+        let mut zero_length_lines = Vec::new();
+        let mut duplicate_vertices = Vec::new();
+
+        for linestring in self {
+            let mut is_degenerate = linestring.euclidean_length() <= tolerance;
+            for window in linestring.0.windows(2) {
+                let (start, end) = (window[0], window[1]);
+                let distance = ((end.x - start.x).powi(2) + (end.y - start.y).powi(2)).sqrt();
+                if distance <= tolerance {
+                    duplicate_vertices.push(Point::from(end));
+                    is_degenerate = true;
+                }
+            }
+            if is_degenerate {
+                zero_length_lines.push(linestring);
+            }
+        }
+
+        let mut geometry_errors = Vec::new();
+        if !duplicate_vertices.is_empty() {
+            geometry_errors.push(TopologyError::Point(duplicate_vertices));
+        }
+        if !zero_length_lines.is_empty() {
+            geometry_errors.push(TopologyError::LineString(zero_length_lines));
+        }
+        if geometry_errors.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(geometry_errors)
+        }
+    }
+}
+
+impl<T: GeoFloat> MustNotHaveZeroLength<T> for Vec<Polygon<T>> {
+    fn must_not_have_zero_length(self, tolerance: T) -> TopologyResult<T> {
+        let slivers: Vec<Polygon<T>> = self
+            .into_iter()
+            .filter(|polygon| polygon.unsigned_area() <= tolerance)
+            .collect();
+        if slivers.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::Polygon(slivers)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{line_string, polygon};
+
+    #[test]
+    fn zero_length_line_is_reported() {
+        let input = vec![
+            line_string![(x: 1., y: 1.), (x: 1., y: 1.)],
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+        ];
+        let output = vec![line_string![(x: 1., y: 1.), (x: 1., y: 1.)]];
+        assert_eq!(
+            input.must_not_have_zero_length(0.0).unwrap_err_linestring(),
+            &TopologyError::LineString(output)
+        );
+    }
+
+    #[test]
+    fn consecutive_duplicate_vertex_is_reported() {
+        let input = vec![line_string![
+            (x: 0., y: 0.), (x: 1., y: 1.), (x: 1., y: 1.), (x: 2., y: 2.)
+        ]];
+        let result = input.clone().must_not_have_zero_length(0.0);
+        assert_eq!(
+            result.unwrap_err_point(),
+            &TopologyError::Point(vec![Point::new(1., 1.)])
+        );
+        assert_eq!(result.unwrap_err_linestring(), &TopologyError::LineString(input));
+    }
+
+    #[test]
+    fn valid_line_is_untouched() {
+        let input = vec![line_string![(x: 0., y: 0.), (x: 1., y: 1.)]];
+        assert!(input.must_not_have_zero_length(0.0).is_valid());
+    }
+
+    #[test]
+    fn sliver_polygon_is_reported() {
+        let input = vec![polygon![(x: 0., y: 0.), (x: 1e-8, y: 0.), (x: 0., y: 1e-8)]];
+        let output = input.clone();
+        assert_eq!(
+            input.must_not_have_zero_length(1e-6).unwrap_err_polygon(),
+            &TopologyError::Polygon(output)
+        );
+    }
+
+    #[test]
+    fn regular_polygon_is_valid() {
+        let input = vec![polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.)]];
+        assert!(input.must_not_have_zero_length(1e-6).is_valid());
+    }
+}
@@ -1,14 +1,29 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::{
-    util::{explode_linestrings, intersections, linestring_inner_points, sweep_points_to_points},
+    util::{
+        classify_intersections, explode_linestrings, intersections, linestring_inner_points,
+        snap_key, sweep_points_to_points, Intersection, SnapKey,
+    },
     TopologyError, TopologyResult,
 };
-use geo::{sweep::SweepPoint, GeoFloat, LineString, Point};
+use geo::{sweep::SweepPoint, Coord, GeoFloat, LineString, Point};
 use itertools::Itertools;
 
 pub trait MustNotIntersect<T: GeoFloat> {
     fn must_not_intersect(&self) -> TopologyResult<T>;
+    /// Like [`Self::must_not_intersect`], but tags every interaction as a clean
+    /// [`Intersection::Crossing`] or a collinear [`Intersection::Overlap`], so callers can treat
+    /// the two categories differently (an overlap is almost always a digitizing error; a crossing
+    /// may be a legitimate feature depending on the data).
+    fn must_not_intersect_detailed(&self) -> Vec<Intersection<T>>;
+    /// Tolerance-aware variant of [`Self::must_not_intersect`]. An improper intersection point is
+    /// reported when its snapped bucket or one of the 8 neighbouring buckets (the way QGIS'
+    /// topology plugin snaps noisy digitizing) holds an inner vertex shared by two or more
+    /// linestrings, where the exact path requires the point to equal such a vertex. Proper
+    /// crossings and collinear overlaps are reported as in the exact path. A non-positive
+    /// `tolerance` falls back to the exact-match path.
+    fn must_not_intersect_within(&self, tolerance: T) -> TopologyResult<T>;
 }
 
 impl<T: GeoFloat + Send + Sync> MustNotIntersect<T> for Vec<LineString<T>> {
@@ -52,4 +67,61 @@ impl<T: GeoFloat + Send + Sync> MustNotIntersect<T> for Vec<LineString<T>> {
             TopologyResult::Errors(geometry_errors)
         }
     }
+
+    fn must_not_intersect_detailed(&self) -> Vec<Intersection<T>> {
+        classify_intersections(explode_linestrings(self))
+    }
+
+    fn must_not_intersect_within(&self, tolerance: T) -> TopologyResult<T> {
+        if tolerance <= T::zero() {
+            return self.must_not_intersect();
+        }
+        let mut endpoints = linestring_inner_points(self);
+        endpoints.sort();
+        let lines = explode_linestrings(self);
+        let subset = endpoints
+            .into_iter()
+            .dedup_with_count()
+            .filter_map(|(size, item)| if size > 1 { Some(item) } else { None })
+            .collect_vec();
+
+        let mut buckets: HashMap<SnapKey, usize> = HashMap::new();
+        for point in &subset {
+            let coord = Coord {
+                x: point.x,
+                y: point.y,
+            };
+            *buckets.entry(snap_key(coord, tolerance)).or_insert(0) += 1;
+        }
+
+        let (lines, (proper, improper)) = intersections::<T, SweepPoint<T>, SweepPoint<T>>(lines);
+        let mut points: BTreeSet<SweepPoint<T>> = improper
+            .into_iter()
+            .filter(|point| {
+                let coord = Coord {
+                    x: point.x,
+                    y: point.y,
+                };
+                let (kx, ky) = snap_key(coord, tolerance);
+                (kx - 1..=kx + 1).any(|x| (ky - 1..=ky + 1).any(|y| buckets.contains_key(&(x, y))))
+            })
+            .collect();
+        // Extend with the proper intersections.
+        points.extend(proper);
+        let points: Vec<Point<T>> = sweep_points_to_points(points).into_iter().collect();
+        let linestrings: Vec<LineString<T>> = lines.into_iter().map_into().collect();
+
+        let mut geometry_errors = Vec::new();
+        if !points.is_empty() {
+            geometry_errors.push(TopologyError::Point(points))
+        }
+        if !linestrings.is_empty() {
+            geometry_errors.push(TopologyError::LineString(linestrings))
+        }
+        if geometry_errors.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(geometry_errors)
+        }
+    }
 }
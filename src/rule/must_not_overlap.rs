@@ -3,11 +3,15 @@ use crate::{
     TopologyError, GeometryType, TopologyResult,
 };
 use geo::{
-    sweep::SweepPoint, BooleanOps, Contains, GeoFloat, HasDimensions,
-    Intersects, Line, LineString, LinesIter, Point, Polygon,
+    algorithm::Relate, sweep::SweepPoint, BooleanOps, BoundingRect, Contains, Coord, CoordsIter,
+    GeoFloat, HasDimensions, Intersects, Line, LineString, LinesIter, Point, Polygon,
+    PreparedGeometry,
 };
 use itertools::Itertools;
+use num_traits::ToPrimitive;
+use rayon::prelude::*;
 use rstar::RTree;
+use std::collections::HashMap;
 use std::ptr::addr_of;
 
 pub trait MustNotOverlap<T: GeoFloat, I: GeometryType<T>, O: GeometryType<T>> {
@@ -19,9 +23,48 @@ pub trait MustNotSelfOverlap<T: GeoFloat> {
     fn must_not_self_overlap(self) -> TopologyResult<T>;
 }
 
+/// Like [`MustNotOverlap::must_not_overlap`], but for polygon batches too large to sweep as a
+/// single global pass.
+pub trait MustNotOverlapTiled<T: GeoFloat> {
+    /// Subdivides the overall bounding rect into square tiles `tile_width` wide (the bounds are
+    /// floored/ceiled out to tile boundaries first, as a standard `tile_bbox` routine would),
+    /// assigns each polygon to every tile its bounding rect touches, then runs
+    /// [`MustNotOverlap::must_not_overlap`] independently per tile in parallel with rayon. A
+    /// polygon pair overlapping near a tile seam is found once per straddled tile, so the
+    /// resulting overlap polygons are merged back together wherever their areas touch, to match
+    /// what a single global pass over `self` would have reported.
+    fn must_not_overlap_tiled(self, tile_width: T) -> TopologyResult<T>;
+}
+
+/// Like [`MustNotOverlap::must_not_overlap_with`], but only for lines, and tolerant of the
+/// near-collinearity that's common in real digitized data rather than requiring exact
+/// containment.
+pub trait MustNotOverlapWithTolerance<T: GeoFloat> {
+    /// Reports the portions of `self` that are collinear and overlapping (not merely
+    /// crossing) with `other`, within `tolerance`. Mirrors turf's `lineOverlap`: segments are
+    /// considered collinear when both the cross product of their direction vectors and the
+    /// cross product of the offset between their start points and one direction vector fall
+    /// within `tolerance`; the overlap is then the 1-D intersection of their projections onto
+    /// their shared axis. Adjacent overlapping sub-segments are merged back into contiguous
+    /// [`LineString`]s before being reported.
+    fn must_not_overlap_with_tolerance(self, other: Vec<LineString<T>>, tolerance: T) -> TopologyResult<T>;
+}
+
+/// Builds a [`PreparedGeometry`] for every polygon in `tree`, keyed by the polygon's address, so
+/// a polygon that shows up in many RTree candidate pairs only pays the cost of preparing its
+/// edge/relate index once rather than once per pair.
+fn prepare_polygons<T: GeoFloat>(
+    tree: &RTree<Polygon<T>>,
+) -> HashMap<*const Polygon<T>, PreparedGeometry<'_, Polygon<T>>> {
+    tree.iter()
+        .map(|polygon| (addr_of!(*polygon), PreparedGeometry::from(polygon)))
+        .collect()
+}
+
 impl<T: GeoFloat + Send + Sync> MustNotOverlap<T, Polygon<T>, Polygon<T>> for Vec<Polygon<T>> {
     fn must_not_overlap(self) -> TopologyResult<T> {
         let polygons = RTree::bulk_load(self);
+        let prepared = prepare_polygons(&polygons);
         // We make this addresses container to avoid duplicate geometries.
         // The 'intersection_candidates_with_other_tree' method will yield both
         // (Polygon1, Polygon2) and (Polygon2, Polygon1).
@@ -35,7 +78,9 @@ impl<T: GeoFloat + Send + Sync> MustNotOverlap<T, Polygon<T>, Polygon<T>> for Ve
                 let address = (addr_of!(*polygon), addr_of!(*other));
                 if !std::ptr::addr_eq(polygon, other)
                     && !addresses.contains(&(address.1, address.0))
-                    && polygon.intersects(other)
+                    && prepared[&address.0]
+                        .relate(&prepared[&address.1])
+                        .is_intersects()
                 {
                     addresses.push(address);
                     let intersection = polygon.intersection(other);
@@ -57,10 +102,15 @@ impl<T: GeoFloat + Send + Sync> MustNotOverlap<T, Polygon<T>, Polygon<T>> for Ve
     fn must_not_overlap_with(self, others: Vec<Polygon<T>>) -> TopologyResult<T> {
         let polygons = RTree::bulk_load(self);
         let others = RTree::bulk_load(others);
+        let prepared_polygons = prepare_polygons(&polygons);
+        let prepared_others = prepare_polygons(&others);
         let geometry_errors: Vec<_> = polygons
             .intersection_candidates_with_other_tree(&others)
             .filter_map(|(polygon, other)| {
-                if polygon.intersects(other) {
+                let relates = prepared_polygons[&addr_of!(*polygon)]
+                    .relate(&prepared_others[&addr_of!(*other)])
+                    .is_intersects();
+                if relates {
                     let intersection = polygon.intersection(other);
                     if !intersection.is_empty() {
                         return Some(intersection.into_iter());
@@ -78,6 +128,114 @@ impl<T: GeoFloat + Send + Sync> MustNotOverlap<T, Polygon<T>, Polygon<T>> for Ve
     }
 }
 
+/// Merges `polygons` that intersect into one another, repeatedly, until no intersecting pair
+/// remains whose union is a single polygon (a pair touching at a single point is kept as two).
+/// Mirrors [`merge_adjacent_segments`]'s pairwise-merge-until-stable approach, just for polygon
+/// unions instead of linestring endpoints.
+fn merge_overlapping_polygons<T: GeoFloat>(mut polygons: Vec<Polygon<T>>) -> Vec<Polygon<T>> {
+    loop {
+        let mut merged_at = None;
+        'outer: for i in 0..polygons.len() {
+            for j in (i + 1)..polygons.len() {
+                if polygons[i].intersects(&polygons[j]) {
+                    let union = polygons[i].union(&polygons[j]);
+                    if union.0.len() == 1 {
+                        let union = union.0.into_iter().next().unwrap();
+                        merged_at = Some((i, j, union));
+                        break 'outer;
+                    }
+                }
+            }
+        }
+        match merged_at {
+            Some((i, j, merged)) => {
+                polygons[i] = merged;
+                polygons.remove(j);
+            }
+            None => break,
+        }
+    }
+    polygons
+}
+
+impl<T: GeoFloat + Send + Sync> MustNotOverlapTiled<T> for Vec<Polygon<T>> {
+    fn must_not_overlap_tiled(self, tile_width: T) -> TopologyResult<T> {
+        let bounds = self.iter().fold(None, |acc, polygon| {
+            let rect = polygon.bounding_rect();
+            Some(match acc {
+                None => rect,
+                Some(acc) => {
+                    geo::Rect::new(
+                        Coord {
+                            x: acc.min().x.min(rect.min().x),
+                            y: acc.min().y.min(rect.min().y),
+                        },
+                        Coord {
+                            x: acc.max().x.max(rect.max().x),
+                            y: acc.max().y.max(rect.max().y),
+                        },
+                    )
+                }
+            })
+        });
+        let bounds = match bounds {
+            Some(bounds) => bounds,
+            None => return TopologyResult::Valid,
+        };
+        let tile_min_x = (bounds.min().x / tile_width).floor() * tile_width;
+        let tile_min_y = (bounds.min().y / tile_width).floor() * tile_width;
+
+        // Assign every polygon to each tile its bounding rect touches, so a polygon straddling
+        // several tiles is checked against everything overlapping it in every one of them.
+        let mut tiles: HashMap<(i64, i64), Vec<Polygon<T>>> = HashMap::new();
+        for polygon in &self {
+            let rect = polygon.bounding_rect();
+            let col_start = ((rect.min().x - tile_min_x) / tile_width)
+                .floor()
+                .to_i64()
+                .unwrap_or(0);
+            let col_end = ((rect.max().x - tile_min_x) / tile_width)
+                .floor()
+                .to_i64()
+                .unwrap_or(0);
+            let row_start = ((rect.min().y - tile_min_y) / tile_width)
+                .floor()
+                .to_i64()
+                .unwrap_or(0);
+            let row_end = ((rect.max().y - tile_min_y) / tile_width)
+                .floor()
+                .to_i64()
+                .unwrap_or(0);
+            for col in col_start..=col_end {
+                for row in row_start..=row_end {
+                    tiles.entry((col, row)).or_default().push(polygon.clone());
+                }
+            }
+        }
+
+        let overlaps: Vec<Polygon<T>> = tiles
+            .into_par_iter()
+            .flat_map(|(_, polygons)| match polygons.must_not_overlap() {
+                TopologyResult::Valid => Vec::new(),
+                TopologyResult::Errors(errors) => errors
+                    .into_iter()
+                    .flat_map(|error| match error {
+                        TopologyError::Polygon(polygons) => polygons,
+                        _ => Vec::new(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let overlaps = merge_overlapping_polygons(overlaps);
+        if overlaps.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::Polygon(overlaps)])
+        }
+    }
+}
+
 impl<T: Send + Sync + GeoFloat> MustNotOverlap<T, LineString<T>, Line<T>> for Vec<LineString<T>> {
     fn must_not_overlap(self) -> TopologyResult<T> {
         let lines = explode_linestrings(&self);
@@ -199,6 +357,142 @@ impl<T: GeoFloat> MustNotSelfOverlap<T> for Vec<LineString<T>> {
     }
 }
 
+fn cross2d<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> T {
+    a.x * b.y - a.y * b.x
+}
+
+/// Tests segments `a` and `b` for collinear overlap within `tolerance`: both the cross
+/// product of their direction vectors and the cross product of the offset between their
+/// start points and `a`'s direction must fall within `tolerance` (normalized by `a`'s length,
+/// so `tolerance` behaves like a distance rather than a raw cross-product magnitude). When
+/// collinear, returns the sub-segment of `a` that `b` overlaps, or `None` if they don't
+/// actually overlap (merely collinear, e.g. disjoint on the same infinite line).
+fn collinear_overlap<T: GeoFloat>(a: Line<T>, b: Line<T>, tolerance: T) -> Option<Line<T>> {
+    let direction = Coord {
+        x: a.end.x - a.start.x,
+        y: a.end.y - a.start.y,
+    };
+    let length_squared = direction.x * direction.x + direction.y * direction.y;
+    if length_squared <= T::zero() {
+        return None;
+    }
+    let length = length_squared.sqrt();
+    let db = Coord {
+        x: b.end.x - b.start.x,
+        y: b.end.y - b.start.y,
+    };
+    if (cross2d(direction, db) / length).abs() > tolerance {
+        return None;
+    }
+    let offset = Coord {
+        x: b.start.x - a.start.x,
+        y: b.start.y - a.start.y,
+    };
+    if (cross2d(direction, offset) / length).abs() > tolerance {
+        return None;
+    }
+
+    let unit = Coord {
+        x: direction.x / length,
+        y: direction.y / length,
+    };
+    let project = |point: Coord<T>| -> T {
+        let offset = Coord {
+            x: point.x - a.start.x,
+            y: point.y - a.start.y,
+        };
+        offset.x * unit.x + offset.y * unit.y
+    };
+    let (b_start, b_end) = (project(b.start), project(b.end));
+    let (low, high) = (
+        b_start.min(b_end).max(T::zero()),
+        b_start.max(b_end).min(length),
+    );
+    if high - low <= tolerance {
+        return None;
+    }
+    let point_at = |t: T| Coord {
+        x: a.start.x + unit.x * t,
+        y: a.start.y + unit.y * t,
+    };
+    Some(Line::new(point_at(low), point_at(high)))
+}
+
+/// Merges `segments` sharing an endpoint into contiguous [`LineString`]s, so adjacent
+/// overlapping sub-segments are reported as one feature rather than many tiny pieces.
+fn merge_adjacent_segments<T: GeoFloat>(segments: Vec<Line<T>>) -> Vec<LineString<T>> {
+    fn merge_two<T: GeoFloat>(a: &LineString<T>, b: &LineString<T>) -> Option<LineString<T>> {
+        let (a_start, a_end) = (a.0[0], a.0[a.0.len() - 1]);
+        let (b_start, b_end) = (b.0[0], b.0[b.0.len() - 1]);
+        if a_start == b_start {
+            Some(LineString::from_iter(
+                a.coords_iter().rev().chain(b.coords_iter().skip(1)),
+            ))
+        } else if a_end == b_start {
+            Some(LineString::from_iter(
+                a.coords_iter().chain(b.coords_iter().skip(1)),
+            ))
+        } else if a_end == b_end {
+            Some(LineString::from_iter(
+                a.coords_iter().chain(b.coords_iter().rev().skip(1)),
+            ))
+        } else if a_start == b_end {
+            Some(LineString::from_iter(
+                b.coords_iter().chain(a.coords_iter().skip(1)),
+            ))
+        } else {
+            None
+        }
+    }
+
+    let mut linestrings: Vec<LineString<T>> = segments
+        .into_iter()
+        .map(|line| LineString::from(vec![line.start, line.end]))
+        .collect();
+    loop {
+        let mut merged_at = None;
+        'outer: for i in 0..linestrings.len() {
+            for j in (i + 1)..linestrings.len() {
+                if let Some(merged) = merge_two(&linestrings[i], &linestrings[j]) {
+                    merged_at = Some((i, j, merged));
+                    break 'outer;
+                }
+            }
+        }
+        match merged_at {
+            Some((i, j, merged)) => {
+                linestrings[i] = merged;
+                linestrings.remove(j);
+            }
+            None => break,
+        }
+    }
+    linestrings
+}
+
+impl<T: Send + Sync + GeoFloat> MustNotOverlapWithTolerance<T> for Vec<LineString<T>> {
+    fn must_not_overlap_with_tolerance(
+        self,
+        other: Vec<LineString<T>>,
+        tolerance: T,
+    ) -> TopologyResult<T> {
+        let lines: Vec<Line<T>> = explode_linestrings(&self).into_iter().collect();
+        let others: Vec<Line<T>> = explode_linestrings(&other).into_iter().collect();
+        let lines_tree: RTree<Line<T>> = RTree::bulk_load(lines);
+        let others_tree = RTree::bulk_load(others);
+        let overlaps: Vec<Line<T>> = lines_tree
+            .intersection_candidates_with_other_tree(&others_tree)
+            .filter_map(|(line, other)| collinear_overlap(*line, *other, tolerance))
+            .collect();
+        if overlaps.is_empty() {
+            return TopologyResult::Valid;
+        }
+        TopologyResult::Errors(vec![TopologyError::LineString(merge_adjacent_segments(
+            overlaps,
+        ))])
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -308,5 +602,18 @@ mod tests {
                 &TopologyError::Polygon(output)
             );
         }
+
+        #[test]
+        fn tiled() {
+            let input = vec![
+                polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)],
+                polygon![(x: 0.25, y: 0.25), (x: 0.75, y: 0.25), (x: 0.75, y: 0.75), (x: 0.25, y: 0.75), (x: 0.25, y: 0.25)],
+            ];
+            let output = vec![input[0].intersection(&input[1]).into_iter().next().unwrap()];
+            assert_eq!(
+                input.must_not_overlap_tiled(10.).unwrap_err_polygon(),
+                &TopologyError::Polygon(output)
+            );
+        }
     }
 }
@@ -0,0 +1,25 @@
+use crate::util::{polygon_overlaps, PartitionedPolygons};
+use crate::{TopologyError, TopologyResult};
+use geo::GeoFloat;
+
+pub trait MustNotOverlapWithinMultipolygon<T: GeoFloat + Send + Sync> {
+    /// Reports every overlap between two polygons of the same input set, per the OGC rule that a
+    /// `MultiPolygon`'s constituent polygons must not have overlapping interiors and may only
+    /// touch at finitely many boundary points. Thin wrapper around
+    /// [`crate::util::polygon_overlaps`] that turns its offending regions into a [`TopologyError`].
+    fn must_not_overlap_within_multipolygon(self) -> TopologyResult<T>;
+}
+
+impl<T: GeoFloat + Send + Sync> MustNotOverlapWithinMultipolygon<T> for PartitionedPolygons<T> {
+    fn must_not_overlap_within_multipolygon(self) -> TopologyResult<T> {
+        let regions = polygon_overlaps(self)
+            .into_iter()
+            .map(|overlap| overlap.region)
+            .collect::<Vec<_>>();
+        if regions.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(vec![TopologyError::MultiPolygon(regions)])
+        }
+    }
+}
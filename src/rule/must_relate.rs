@@ -0,0 +1,122 @@
+use crate::{TopologyError, TopologyResult};
+use geo::{GeoFloat, Geometry, Relate};
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use rstar::RTree;
+use std::collections::HashSet;
+use std::ptr::addr_of;
+
+/// Generalizes `must_be_inside`, `must_not_overlap` and friends into a single rule driven by a
+/// DE-9IM pattern (e.g. `"T*F**F***"` for within, `"T*T***T**"` for overlaps) instead of a
+/// bespoke predicate function. See [`geo::IntersectionMatrix::matches`] for the pattern
+/// grammar: each of the 9 cells matches `T` (any dimension), `F` (disjoint), `0`/`1`/`2`
+/// (exact dimension), or `*` (don't care).
+pub trait MustRelate<T: GeoFloat + Send + Sync> {
+    /// Returns every geometry in `self` whose relationship to every intersection candidate in
+    /// `other` fails to match `pattern`. A geometry with no candidates in `other` at all is
+    /// reported too, since there was nothing for it to match against.
+    fn must_relate(self, other: Vec<Geometry<T>>, pattern: &str) -> TopologyResult<T>;
+}
+
+impl<T: GeoFloat + Send + Sync> MustRelate<T> for Vec<Geometry<T>> {
+    fn must_relate(self, other: Vec<Geometry<T>>, pattern: &str) -> TopologyResult<T> {
+        let geometries = RTree::bulk_load(self);
+        let targets = RTree::bulk_load(other);
+        // Track matches by RTree node address rather than geometry value, the same
+        // `addr_of!`/`HashSet<*const _>` pattern the rest of this crate uses for dedup — two
+        // distinct input geometries that happen to have equal coordinates must not be
+        // conflated, since only one of them may actually have matched a candidate.
+        let matching: HashSet<*const Geometry<T>> = geometries
+            .intersection_candidates_with_other_tree(&targets)
+            .par_bridge()
+            .filter_map(|(geometry, target)| {
+                let matrix = geometry.relate(target);
+                if matrix.matches(pattern).unwrap_or(false) {
+                    Some(addr_of!(*geometry))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let non_matching: Vec<Geometry<T>> = geometries
+            .iter()
+            .par_bridge()
+            .filter(|geometry| !matching.contains(&addr_of!(**geometry)))
+            .cloned()
+            .collect();
+        if non_matching.is_empty() {
+            TopologyResult::Valid
+        } else {
+            TopologyResult::Errors(classify(non_matching))
+        }
+    }
+}
+
+/// Buckets a mixed batch of non-matching geometries into one [`TopologyError`] per concrete
+/// variant present, the same way [`super::must_not_be_multipart`] buckets multipart geometries.
+fn classify<T: GeoFloat>(geometries: Vec<Geometry<T>>) -> Vec<TopologyError<T>> {
+    let mut points = Vec::new();
+    let mut linestrings = Vec::new();
+    let mut polygons = Vec::new();
+    let mut multipoints = Vec::new();
+    let mut multilinestrings = Vec::new();
+    let mut multipolygons = Vec::new();
+    for geometry in geometries {
+        match geometry {
+            Geometry::Point(point) => points.push(point),
+            Geometry::LineString(linestring) => linestrings.push(linestring),
+            Geometry::Polygon(polygon) => polygons.push(polygon),
+            Geometry::MultiPoint(multipoint) => multipoints.push(multipoint),
+            Geometry::MultiLineString(multilinestring) => multilinestrings.push(multilinestring),
+            Geometry::MultiPolygon(multipolygon) => multipolygons.push(multipolygon),
+            _ => (),
+        }
+    }
+    let mut errors = Vec::new();
+    if !points.is_empty() {
+        errors.push(TopologyError::Point(points));
+    }
+    if !linestrings.is_empty() {
+        errors.push(TopologyError::LineString(linestrings));
+    }
+    if !polygons.is_empty() {
+        errors.push(TopologyError::Polygon(polygons));
+    }
+    if !multipoints.is_empty() {
+        errors.push(TopologyError::MultiPoint(multipoints));
+    }
+    if !multilinestrings.is_empty() {
+        errors.push(TopologyError::MultiLineString(multilinestrings));
+    }
+    if !multipolygons.is_empty() {
+        errors.push(TopologyError::MultiPolygon(multipolygons));
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+
+    use geo::{point, polygon};
+
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let polygon = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)];
+        let points = vec![Geometry::Point(point! {x: 0.5, y: 0.5})];
+        let targets = vec![Geometry::Polygon(polygon)];
+        // "within": the point's interior intersects the polygon's interior, and nothing of
+        // the point lies in the polygon's exterior.
+        let result = points.must_relate(targets, "T*F**F***");
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn invalid() {
+        let polygon = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)];
+        let points = vec![Geometry::Point(point! {x: 5., y: 5.})];
+        let targets = vec![Geometry::Polygon(polygon)];
+        let result = points.must_relate(targets, "T*F**F***");
+        assert!(!result.is_valid());
+    }
+}
@@ -1,8 +1,6 @@
-mod there_are_no_dangles;
 mod must_not_intersect;
 mod must_not_overlap;
 
-pub use there_are_no_dangles::there_are_no_dangles;
 pub use must_not_intersect::must_not_intersect;
 pub use must_not_overlap::must_not_overlap;
 
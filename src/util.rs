@@ -1,9 +1,24 @@
 mod geometry;
+mod graph;
 mod io;
+mod monotonic_polygons;
+mod polygon_overlaps;
+mod wkt;
 
 pub use geometry::{
-    coords_to_points, explode_linestrings, flatten_linestrings, flatten_points, flatten_polygons,
-    intersections, is_line, is_point, is_polygon, linestring_endpoints, linestring_inner_points,
-    sweep_points_to_points,
+    classify_intersections, coords_to_points, explode_geometries, explode_linestrings,
+    filter_by_extent, flatten_linestrings, flatten_points, flatten_polygons, intersections,
+    is_line, is_point, is_polygon, linestring_endpoints, linestring_inner_points,
+    multipolygon_interior_point, polygon_interior_point, snap_coord, snap_key,
+    sweep_points_to_points, try_flatten_linestrings, try_flatten_points, try_flatten_polygons,
+    GeometryKind, Intersection, PartitionedPolygons, SnapKey, UnexpectedGeometry,
 };
-pub use io::{open_dataset, create_dataset, geometries_to_file, GdalDrivers};
+pub use graph::{GraphEdge, GraphNode, NetworkGraph};
+pub use monotonic_polygons::MonotonicPolygons;
+pub use polygon_overlaps::{polygon_overlaps, PolygonOverlap};
+pub use io::manage;
+pub use io::{
+    create_dataset, feature_attributes, geometries_from_wkt, geometries_to_file, geometries_to_wkt,
+    harmonize_srs, open_dataset, validate_srs, Attribute, GdalDrivers, OutputFormat, SrsMode,
+};
+pub use wkt::{from_wkt_generic, to_wkt, WktParseError};
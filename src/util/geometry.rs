@@ -1,9 +1,11 @@
 use geo::{
     algorithm::LineIntersection,
     sweep::{Intersections, SweepPoint},
-    Coord, GeoFloat, Geometry, Line, LineString, LinesIter, MultiPolygon, Point, Polygon,
+    Area, BoundingRect, Coord, GeoFloat, Geometry, Intersects, Line, LineString, LinesIter,
+    MultiPolygon, Point, Polygon, Rect,
 };
 use itertools::{Either, Itertools};
+use num_traits::ToPrimitive;
 use rayon::{iter::ParallelIterator, prelude::*};
 use std::collections::BTreeSet;
 
@@ -111,6 +113,52 @@ where
             }
         }))
     }
+
+    /// Fallible, `GeometryCollection`-aware counterpart to [`Self::from_geometries`]: reports the
+    /// first unexpected geometry as an [`UnexpectedGeometry`] instead of panicking on it.
+    pub fn try_from_geometries(
+        geometries: Vec<Geometry<T>>,
+    ) -> Result<PartitionedPolygons<T>, UnexpectedGeometry> {
+        let parts: Vec<(Vec<Polygon<T>>, Vec<MultiPolygon<T>>)> = geometries
+            .into_iter()
+            .enumerate()
+            .par_bridge()
+            .map(|(index, geometry)| try_extract_partitioned_polygons(geometry, index))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut polygons = Vec::new();
+        let mut multipolygons = Vec::new();
+        for (polygon_part, multipolygon_part) in parts {
+            polygons.extend(polygon_part);
+            multipolygons.extend(multipolygon_part);
+        }
+        Ok(PartitionedPolygons(polygons, multipolygons))
+    }
+}
+
+/// Recursive counterpart to [`try_extract_polygons`] that keeps polygons and multipolygons
+/// partitioned rather than flattening them together, for [`PartitionedPolygons::try_from_geometries`].
+fn try_extract_partitioned_polygons<T: GeoFloat>(
+    geometry: Geometry<T>,
+    index: usize,
+) -> Result<(Vec<Polygon<T>>, Vec<MultiPolygon<T>>), UnexpectedGeometry> {
+    match geometry {
+        Geometry::Polygon(polygon) => Ok((vec![polygon], Vec::new())),
+        Geometry::MultiPolygon(multipolygon) => Ok((Vec::new(), vec![multipolygon])),
+        Geometry::GeometryCollection(collection) => collection.into_iter().try_fold(
+            (Vec::new(), Vec::new()),
+            |(mut polygons, mut multipolygons), geometry| {
+                let (nested_polygons, nested_multipolygons) =
+                    try_extract_partitioned_polygons(geometry, index)?;
+                polygons.extend(nested_polygons);
+                multipolygons.extend(nested_multipolygons);
+                Ok((polygons, multipolygons))
+            },
+        ),
+        other => Err(UnexpectedGeometry {
+            index,
+            kind: GeometryKind::from(&other),
+        }),
+    }
 }
 
 impl<T> IntoIterator for PartitionedPolygons<T>
@@ -128,15 +176,197 @@ where
     }
 }
 
-/// Converts Linestring to Line.
+/// Names a [`Geometry`] variant, for error messages from the `try_*` ingestion helpers below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryKind {
+    Point,
+    Line,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    Rect,
+    Triangle,
+}
+
+impl<T: GeoFloat> From<&Geometry<T>> for GeometryKind {
+    fn from(geometry: &Geometry<T>) -> Self {
+        match geometry {
+            Geometry::Point(_) => GeometryKind::Point,
+            Geometry::Line(_) => GeometryKind::Line,
+            Geometry::LineString(_) => GeometryKind::LineString,
+            Geometry::Polygon(_) => GeometryKind::Polygon,
+            Geometry::MultiPoint(_) => GeometryKind::MultiPoint,
+            Geometry::MultiLineString(_) => GeometryKind::MultiLineString,
+            Geometry::MultiPolygon(_) => GeometryKind::MultiPolygon,
+            Geometry::GeometryCollection(_) => GeometryKind::GeometryCollection,
+            Geometry::Rect(_) => GeometryKind::Rect,
+            Geometry::Triangle(_) => GeometryKind::Triangle,
+        }
+    }
+}
+
+impl std::fmt::Display for GeometryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Raised by the `try_*` ingestion helpers in this module when a batch contains a geometry that
+/// doesn't match what the caller asked to extract: unlike their panicking counterparts
+/// (`flatten_linestrings` and friends), the offending feature is named instead of aborting the
+/// whole batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnexpectedGeometry {
+    /// Position of the offending feature in the input batch (the outermost geometry's index,
+    /// even when the mismatch is nested inside a `GeometryCollection`).
+    pub index: usize,
+    /// The geometry variant found instead of what was expected.
+    pub kind: GeometryKind,
+}
+
+impl std::fmt::Display for UnexpectedGeometry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unexpected {} geometry at index {}.",
+            self.kind, self.index
+        )
+    }
+}
+
+impl std::error::Error for UnexpectedGeometry {}
+
+/// Recursively extracts every linestring out of `geometry`, descending into nested
+/// `GeometryCollection`s rather than rejecting them, and tagging any other unexpected variant
+/// with `index` (the position of the outermost geometry this call started from).
+fn try_extract_linestrings<T: GeoFloat>(
+    geometry: Geometry<T>,
+    index: usize,
+) -> Result<Vec<LineString<T>>, UnexpectedGeometry> {
+    match geometry {
+        Geometry::LineString(linestring) => Ok(vec![linestring]),
+        Geometry::MultiLineString(multilinestring) => Ok(multilinestring.into_iter().collect()),
+        Geometry::Line(line) => Ok(vec![line.into()]),
+        Geometry::GeometryCollection(collection) => collection
+            .into_iter()
+            .map(|geometry| try_extract_linestrings(geometry, index))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|nested| nested.into_iter().flatten().collect()),
+        other => Err(UnexpectedGeometry {
+            index,
+            kind: GeometryKind::from(&other),
+        }),
+    }
+}
+
+/// Fallible, `GeometryCollection`-aware counterpart to [`flatten_linestrings`]: reports the first
+/// unexpected geometry as an [`UnexpectedGeometry`] instead of panicking on it.
+pub fn try_flatten_linestrings<T: GeoFloat + Send + Sync>(
+    geometries: Vec<Geometry<T>>,
+) -> Result<Vec<LineString<T>>, UnexpectedGeometry> {
+    geometries
+        .into_iter()
+        .enumerate()
+        .par_bridge()
+        .map(|(index, geometry)| try_extract_linestrings(geometry, index))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|nested| nested.into_iter().flatten().collect())
+}
+
+/// Recursive counterpart to [`try_extract_linestrings`], for points.
+fn try_extract_points<T: GeoFloat>(
+    geometry: Geometry<T>,
+    index: usize,
+) -> Result<Vec<Point<T>>, UnexpectedGeometry> {
+    match geometry {
+        Geometry::Point(point) => Ok(vec![point]),
+        Geometry::MultiPoint(points) => Ok(points.into_iter().collect()),
+        Geometry::GeometryCollection(collection) => collection
+            .into_iter()
+            .map(|geometry| try_extract_points(geometry, index))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|nested| nested.into_iter().flatten().collect()),
+        other => Err(UnexpectedGeometry {
+            index,
+            kind: GeometryKind::from(&other),
+        }),
+    }
+}
+
+/// Fallible, `GeometryCollection`-aware counterpart to [`flatten_points`]: reports the first
+/// unexpected geometry as an [`UnexpectedGeometry`] instead of panicking on it.
+pub fn try_flatten_points<T: GeoFloat + Send + Sync>(
+    geometries: Vec<Geometry<T>>,
+) -> Result<Vec<Point<T>>, UnexpectedGeometry> {
+    geometries
+        .into_iter()
+        .enumerate()
+        .par_bridge()
+        .map(|(index, geometry)| try_extract_points(geometry, index))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|nested| nested.into_iter().flatten().collect())
+}
+
+/// Recursive counterpart to [`try_extract_linestrings`], for polygons.
+fn try_extract_polygons<T: GeoFloat>(
+    geometry: Geometry<T>,
+    index: usize,
+) -> Result<Vec<Polygon<T>>, UnexpectedGeometry> {
+    match geometry {
+        Geometry::Polygon(polygon) => Ok(vec![polygon]),
+        Geometry::MultiPolygon(multipolygon) => Ok(multipolygon.into_iter().collect()),
+        Geometry::GeometryCollection(collection) => collection
+            .into_iter()
+            .map(|geometry| try_extract_polygons(geometry, index))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|nested| nested.into_iter().flatten().collect()),
+        other => Err(UnexpectedGeometry {
+            index,
+            kind: GeometryKind::from(&other),
+        }),
+    }
+}
+
+/// Fallible, `GeometryCollection`-aware counterpart to [`flatten_polygons`]: reports the first
+/// unexpected geometry as an [`UnexpectedGeometry`] instead of panicking on it.
+pub fn try_flatten_polygons<T: GeoFloat + Send + Sync>(
+    geometries: Vec<Geometry<T>>,
+) -> Result<Vec<Polygon<T>>, UnexpectedGeometry> {
+    geometries
+        .into_iter()
+        .enumerate()
+        .par_bridge()
+        .map(|(index, geometry)| try_extract_polygons(geometry, index))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|nested| nested.into_iter().flatten().collect())
+}
+
+/// Extracts every segment of `geometries` as a [Line]: lines, linestrings, polygon exterior and
+/// interior rings, and their multi-variants, all in one pass via geo's [LinesIter], so the
+/// `intersections` sweep can run over polygon boundaries the same way it runs over linestrings
+/// without the caller writing a match arm per geometry kind.
+pub fn explode_geometries<T: GeoFloat + Send + Sync>(geometries: &[Geometry<T>]) -> Vec<Line<T>> {
+    geometries
+        .iter()
+        .par_bridge()
+        .flat_map_iter(|geometry| geometry.lines_iter())
+        .collect()
+}
+
+/// Converts Linestring to Line. A thin wrapper over [explode_geometries] kept for source
+/// compatibility with callers that only have linestrings on hand.
 pub fn explode_linestrings<T: GeoFloat + Send + Sync>(
     linestrings: &Vec<LineString<T>>,
 ) -> Vec<Line<T>> {
-    linestrings
+    let geometries: Vec<Geometry<T>> = linestrings
         .iter()
-        .par_bridge()
-        .flat_map_iter(|linestring| linestring.lines_iter())
-        .collect()
+        .cloned()
+        .map(Geometry::LineString)
+        .collect();
+    explode_geometries(&geometries)
 }
 
 /// Extract inner points (points that are not endpoints) from linestrings.
@@ -222,6 +452,33 @@ where
     (lines, points)
 }
 
+/// A single detected interaction between two intersecting segments: either a clean crossing at a
+/// single point, or a stretch where the two segments run collinear on top of each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Intersection<T: GeoFloat> {
+    Crossing(Point<T>),
+    Overlap(Line<T>),
+}
+
+/// Classifies every pairwise intersection among `lines` as a [`Intersection::Crossing`] (a
+/// nonzero-determinant, single-point intersection, proper or not) or an [`Intersection::Overlap`]
+/// (a zero-determinant, collinear intersection, reported as the overlapping sub-segment). Unlike
+/// [`intersections`], this doesn't dedupe or bucket by proper/improper: it's a flat classification
+/// of every interaction the sweep finds, for callers that need to tell the two kinds apart rather
+/// than just collect offending points and lines.
+pub fn classify_intersections<T: GeoFloat>(
+    lines: impl IntoIterator<Item = Line<T>>,
+) -> Vec<Intersection<T>> {
+    Intersections::from_iter(lines)
+        .map(|vector| match vector.2 {
+            LineIntersection::Collinear { intersection } => Intersection::Overlap(intersection),
+            LineIntersection::SinglePoint { intersection, .. } => {
+                Intersection::Crossing(intersection.into())
+            }
+        })
+        .collect()
+}
+
 /// Converts [Coord] to [Point]
 pub fn coords_to_points<T>(coords: impl IntoIterator<Item = Coord<T>>) -> Vec<Point>
 where
@@ -231,6 +488,82 @@ where
     coords.into_iter().map_into().collect()
 }
 
+/// A hashable key identifying the grid cell a snapped coordinate falls into.
+pub type SnapKey = (i64, i64);
+
+/// Snaps a coordinate onto a regular grid of the given `tolerance` by rounding each ordinate to
+/// the nearest multiple of `tolerance`, so coordinates in the same grid cell collapse onto one
+/// point; two coordinates closer than `tolerance` can still round to adjacent cells. This mirrors the precision-model
+/// grid-snapping GEOS uses to treat near-coincident vertices as coincident.
+pub fn snap_coord<T: GeoFloat>(coord: Coord<T>, tolerance: T) -> Coord<T> {
+    Coord {
+        x: (coord.x / tolerance).round() * tolerance,
+        y: (coord.y / tolerance).round() * tolerance,
+    }
+}
+
+/// Computes the [SnapKey] a coordinate falls into for the given `tolerance`.
+/// Unlike [snap_coord], this is meant to be used as a `HashMap`/`union-find` key, since
+/// `T` itself is not guaranteed to be hashable.
+pub fn snap_key<T: GeoFloat>(coord: Coord<T>, tolerance: T) -> SnapKey {
+    (
+        (coord.x / tolerance)
+            .round()
+            .to_i64()
+            .expect("Failed to convert snapped x to i64."),
+        (coord.y / tolerance)
+            .round()
+            .to_i64()
+            .expect("Failed to convert snapped y to i64."),
+    )
+}
+
+/// Computes a representative point guaranteed to fall strictly inside `polygon`'s area, unlike
+/// a centroid, which can land in a hole. Scans a horizontal line through the vertical center of
+/// `polygon`'s bounding rect, intersects it with every ring (exterior and interiors) to collect
+/// x-crossings, then returns the midpoint of the widest even/odd-parity run between consecutive
+/// crossings — the widest place the horizontal line actually crosses the polygon's interior.
+pub fn polygon_interior_point<T: GeoFloat>(polygon: &Polygon<T>) -> Point<T> {
+    let rect = polygon.bounding_rect();
+    let mid_y = (rect.min().y + rect.max().y) / (T::one() + T::one());
+
+    let mut crossings: Vec<T> = polygon
+        .exterior()
+        .lines_iter()
+        .chain(polygon.interiors().iter().flat_map(|ring| ring.lines_iter()))
+        .filter_map(|line| {
+            let (start, end) = (line.start, line.end);
+            if (start.y <= mid_y && mid_y < end.y) || (end.y <= mid_y && mid_y < start.y) {
+                let t = (mid_y - start.y) / (end.y - start.y);
+                Some(start.x + t * (end.x - start.x))
+            } else {
+                None
+            }
+        })
+        .collect();
+    crossings.sort_by(|a, b| a.partial_cmp(b).expect("Encountered a NaN coordinate."));
+
+    let widest = crossings
+        .chunks_exact(2)
+        .max_by(|a, b| (a[1] - a[0]).partial_cmp(&(b[1] - b[0])).unwrap())
+        .expect("A polygon's bounding-rect midline must cross its boundary at least twice.");
+    Point::new((widest[0] + widest[1]) / (T::one() + T::one()), mid_y)
+}
+
+/// Like [`polygon_interior_point`], but for a [`MultiPolygon`]: returns the interior point of
+/// its largest-area part, since that's the most representative single flag point for the whole
+/// feature. `None` for an empty multipolygon.
+pub fn multipolygon_interior_point<T: GeoFloat>(multipolygon: &MultiPolygon<T>) -> Option<Point<T>> {
+    multipolygon
+        .iter()
+        .max_by(|a, b| {
+            a.unsigned_area()
+                .partial_cmp(&b.unsigned_area())
+                .unwrap()
+        })
+        .map(polygon_interior_point)
+}
+
 /// Converts [SweepPoint] to [Point].
 pub fn sweep_points_to_points<T>(
     sweep_points: impl IntoIterator<Item = SweepPoint<T>>,
@@ -248,3 +581,20 @@ where
         })
         .collect()
 }
+
+/// Restricts `geometries` to those intersecting `extent`, mirroring how QGIS' topology checker
+/// limits checking to the current map view: when `extent` is `None` (the common case), every
+/// geometry is kept as-is, so extent-restricted checking is opt-in and free when unused.
+pub fn filter_by_extent<T, G>(geometries: Vec<G>, extent: Option<Rect<T>>) -> Vec<G>
+where
+    T: GeoFloat,
+    G: Intersects<Rect<T>>,
+{
+    match extent {
+        Some(extent) => geometries
+            .into_iter()
+            .filter(|geometry| geometry.intersects(&extent))
+            .collect(),
+        None => geometries,
+    }
+}
@@ -0,0 +1,95 @@
+//! Builds a simple node/edge graph out of a linestring layer, snapping endpoints onto a
+//! tolerance grid so coincident vertices collapse onto one node, and serializes it to
+//! GraphViz DOT so the connectivity the dangle/intersection rules already compute can be
+//! inspected visually instead of just counted.
+use super::geometry::{snap_key, SnapKey};
+use geo::{Coord, GeoFloat, LineString};
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+
+/// A node of a [`NetworkGraph`]: a stable id, the real (unsnapped) coordinate of its first
+/// occurrence, and the number of edges touching it.
+pub struct GraphNode<T: GeoFloat> {
+    pub id: usize,
+    pub coord: Coord<T>,
+    pub degree: usize,
+}
+
+/// An edge of a [`NetworkGraph`]: the node ids of one linestring's start and end.
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+}
+
+/// A node/edge graph built from a set of linestrings, with endpoints that fall into the same
+/// `tolerance` grid cell collapsed onto the same node.
+pub struct NetworkGraph<T: GeoFloat> {
+    pub nodes: Vec<GraphNode<T>>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl<T: GeoFloat> NetworkGraph<T> {
+    /// Builds a graph from `linestrings`, snapping each endpoint onto a `tolerance` grid
+    /// (see [`crate::util::snap_key`]) so near-coincident vertices produced by slightly
+    /// different digitizing collapse onto the same node.
+    pub fn build(linestrings: &[LineString<T>], tolerance: T) -> Self {
+        let mut ids: HashMap<SnapKey, usize> = HashMap::new();
+        let mut nodes: Vec<GraphNode<T>> = Vec::new();
+        let mut edges = Vec::with_capacity(linestrings.len());
+
+        let mut node_id_for = |coord: Coord<T>, nodes: &mut Vec<GraphNode<T>>| -> usize {
+            let key = snap_key(coord, tolerance);
+            *ids.entry(key).or_insert_with(|| {
+                let id = nodes.len();
+                nodes.push(GraphNode {
+                    id,
+                    coord,
+                    degree: 0,
+                });
+                id
+            })
+        };
+
+        for linestring in linestrings {
+            let start = *linestring
+                .0
+                .first()
+                .expect("Linestring has no coordinates.");
+            let end = *linestring.0.last().expect("Linestring has no coordinates.");
+            let from = node_id_for(start, &mut nodes);
+            let to = node_id_for(end, &mut nodes);
+            nodes[from].degree += 1;
+            nodes[to].degree += 1;
+            edges.push(GraphEdge { from, to });
+        }
+
+        NetworkGraph { nodes, edges }
+    }
+
+    /// Serializes the graph to GraphViz DOT: `digraph { ... }` with `->` edges when
+    /// `directed`, `graph { ... }` with `--` edges otherwise. Degree-1 nodes (dangles) are
+    /// tagged with `color=red` so they stand out when rendered.
+    pub fn to_dot(&self, directed: bool) -> String {
+        let (keyword, edge_op) = if directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = format!("{keyword} {{\n");
+        for node in &self.nodes {
+            let color = if node.degree == 1 { ", color=red" } else { "" };
+            dot.push_str(&format!(
+                "  n{} [pos=\"{},{}\"{}];\n",
+                node.id,
+                node.coord.x.to_f64().unwrap_or_default(),
+                node.coord.y.to_f64().unwrap_or_default(),
+                color,
+            ));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!("  n{} {} n{};\n", edge.from, edge_op, edge.to));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
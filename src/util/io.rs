@@ -1,3 +1,5 @@
+pub mod manage;
+
 use crate::{Dataset, SRSComparison, VectorDataset};
 use anyhow::Context;
 use gdal::{vector::LayerAccess, DatasetOptions, GdalOpenFlags, LayerOptions, Metadata};
@@ -6,6 +8,31 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::PathBuf;
 
+/// True when `path` is a GDAL virtual-filesystem handler path (`/vsizip/`, `/vsicurl/`,
+/// etc.). GDAL resolves these itself, so the local `path.exists()` check must be skipped
+/// for them — a remote or in-archive path never exists on the local filesystem.
+pub fn is_virtual_path(path: &str) -> bool {
+    path.starts_with("/vsi")
+}
+
+/// Builds the progressively longer trailing-extension candidates for `path`'s file name, so
+/// `data.gpkg.zip` yields `["zip", "gpkg.zip"]` (shortest first). This lets compound
+/// extensions (`.shp.zip`, `.gpkg.zip`) be tried against the driver extension set alongside
+/// the plain single-part ones GDAL itself advertises.
+pub fn compound_extension_candidates(path: &str) -> Vec<String> {
+    // Look only at the last path segment, which drops any GDAL virtual-filesystem prefix;
+    // for an in-archive path such as `/vsizip/archive.zip/layer.shp` that segment is the
+    // in-archive `layer.shp`, not the archive's own name.
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let parts: Vec<&str> = file_name.split('.').collect();
+    if parts.len() < 2 {
+        return Vec::new();
+    }
+    (1..parts.len())
+        .map(|take| parts[parts.len() - take..].join("."))
+        .collect()
+}
+
 pub fn open_dataset_gdal(path: &PathBuf) -> anyhow::Result<gdal::Dataset> {
     let options = DatasetOptions {
         open_flags: GdalOpenFlags::GDAL_OF_VECTOR,
@@ -15,7 +42,8 @@ pub fn open_dataset_gdal(path: &PathBuf) -> anyhow::Result<gdal::Dataset> {
 }
 
 pub fn open_dataset(path: &PathBuf, use_gdal: bool) -> anyhow::Result<Dataset> {
-    if !path.exists() {
+    let path_str = path.to_string_lossy().into_owned();
+    if !is_virtual_path(&path_str) && !path.exists() {
         return Err(anyhow::anyhow!(
             "The provided path {:?} does not exist",
             path
@@ -24,12 +52,21 @@ pub fn open_dataset(path: &PathBuf, use_gdal: bool) -> anyhow::Result<Dataset> {
     if use_gdal {
         return Ok(Dataset::GDAL(open_dataset_gdal(path)?))
     }
-    let ext = if let Some(ext) = path.extension() {
-        ext.to_str().unwrap()
-    } else {
-        return Err(anyhow::anyhow!(
-            "The provided file name does not have a valid extension."
-        ));
+    let candidates = compound_extension_candidates(&path_str);
+    // Prefer the longest (most specific) compound suffix that a known driver actually
+    // advertises, e.g. `gpkg.zip` over the bare `zip` it also matches.
+    let ext = candidates
+        .iter()
+        .rev()
+        .find(|candidate| GdalDrivers.infer_driver_name(candidate).is_some())
+        .or_else(|| candidates.last());
+    let ext = match ext {
+        Some(ext) => ext.as_str(),
+        None => {
+            return Err(anyhow::anyhow!(
+                "The provided file name does not have a valid extension."
+            ));
+        }
     };
 
     match ext {
@@ -43,13 +80,27 @@ pub fn open_dataset(path: &PathBuf, use_gdal: bool) -> anyhow::Result<Dataset> {
 }
 
 pub fn create_dataset(out_path: &PathBuf, driver: Option<String>) -> anyhow::Result<gdal::Dataset> {
-    // If driver is not provided, attempt to infer it from the file extension.
+    // If driver is not provided, attempt to infer it from the file extension, skipping any
+    // ranked candidate that turns out not to be writeable rather than hard-failing on the
+    // first (preferred) match.
     let driver_name = driver.unwrap_or_else(|| {
-        let driver = GdalDrivers
-            .infer_driver_name(out_path.extension().expect(format!("Path {out_path:?} does not have a valid extension.").as_str()).to_str().unwrap())
-            .expect("Could not infer driver by file extension. Consider specifying the GDAL_DRIVER environment variable.");
-        driver.1.get("write").unwrap().clone().expect(format!("Driver {} is not writeable.", driver.0).as_str());
-        driver.0
+        let path_str = out_path.to_string_lossy().into_owned();
+        let candidates = compound_extension_candidates(&path_str);
+        if candidates.is_empty() {
+            panic!("Path {out_path:?} does not have a valid extension.");
+        }
+        // Try the most specific (longest) compound suffix first, e.g. `gpkg.zip` before `zip`.
+        candidates
+            .iter()
+            .rev()
+            .find_map(|extension| {
+                GdalDrivers
+                    .infer_drivers(extension, true, false)
+                    .into_iter()
+                    .find(|(_, properties)| properties.get("write").unwrap().is_some())
+            })
+            .expect("Could not infer a writeable driver by file extension. Consider specifying the GDAL_DRIVER environment variable.")
+            .0
     });
     let drv = gdal::DriverManager::get_driver_by_name(&driver_name)
         .expect(format!("Driver {driver_name} does not exist.").as_str());
@@ -59,35 +110,169 @@ pub fn create_dataset(out_path: &PathBuf, driver: Option<String>) -> anyhow::Res
     Ok(dataset)
 }
 
+/// One attribute field to attach to a feature written by [`geometries_to_file`], so error
+/// layers can carry metadata (error type, feature id, rule name) alongside the geometry.
+pub struct Attribute {
+    pub name: String,
+    pub value: gdal::vector::FieldValue,
+}
+
+fn ogr_field_type(value: &gdal::vector::FieldValue) -> gdal::vector::OGRFieldType::Type {
+    use gdal::vector::{FieldValue, OGRFieldType};
+    match value {
+        FieldValue::IntegerValue(_) => OGRFieldType::OFTInteger,
+        FieldValue::Integer64Value(_) => OGRFieldType::OFTInteger64,
+        FieldValue::RealValue(_) => OGRFieldType::OFTReal,
+        FieldValue::StringValue(_) => OGRFieldType::OFTString,
+        FieldValue::DateValue(_) => OGRFieldType::OFTDate,
+        FieldValue::DateTimeValue(_) => OGRFieldType::OFTDateTime,
+        _ => OGRFieldType::OFTString,
+    }
+}
+
+/// Reads every field of `feature` into a parallel list of [`Attribute`]s, the way GRASS'
+/// `copy_tabs` copies a feature's attribute row into an output layer. Null fields are skipped,
+/// matching how [`write_features`] only attaches the attributes it's given.
+pub fn feature_attributes(feature: &gdal::vector::Feature) -> Vec<Attribute> {
+    feature
+        .fields()
+        .filter_map(|(name, value)| value.map(|value| Attribute { name, value }))
+        .collect()
+}
+
+fn write_features(
+    layer: &mut gdal::vector::Layer,
+    geometries: Vec<gdal::vector::Geometry>,
+    attributes: &[Vec<Attribute>],
+) -> anyhow::Result<()> {
+    for (index, geometry) in geometries.into_iter().enumerate() {
+        match attributes.get(index) {
+            Some(fields) if !fields.is_empty() => {
+                let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+                let values: Vec<gdal::vector::FieldValue> =
+                    fields.iter().map(|field| field.value.clone()).collect();
+                layer
+                    .create_feature_fields(geometry, &names, &values)
+                    .with_context(|| "Failed to write a geometry with attributes.")?;
+            }
+            _ => {
+                layer
+                    .create_feature(geometry)
+                    .with_context(|| "Failed to write a geometry.")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes `geometries` to `out_path`, optionally carrying a parallel vector of per-feature
+/// [`Attribute`]s (e.g. error type, feature id, rule name) and optionally appending to an
+/// already-existing layer rather than always creating a fresh dataset.
+///
+/// `append` opens `out_path` with `GDAL_OF_UPDATE` and writes into its first layer (or the
+/// layer named in `options`, if one is given), which lets multiple topology rules accumulate
+/// their flagged geometries into one attributed error layer across several calls.
 pub fn geometries_to_file(
     geometries: Vec<gdal::vector::Geometry>,
     out_path: &PathBuf,
     driver: Option<String>,
     options: Option<LayerOptions>,
-) {
+    attributes: Option<Vec<Vec<Attribute>>>,
+    append: bool,
+) -> anyhow::Result<()> {
+    let attributes = attributes.unwrap_or_default();
+
+    if append {
+        let dataset_options = DatasetOptions {
+            open_flags: GdalOpenFlags::GDAL_OF_VECTOR | GdalOpenFlags::GDAL_OF_UPDATE,
+            ..Default::default()
+        };
+        let mut ds = gdal::Dataset::open_ex(out_path, dataset_options)
+            .with_context(|| format!("Failed to open {out_path:?} for appending."))?;
+        let mut layer = match options.as_ref().map(|options| options.name) {
+            Some(name) if !name.is_empty() => ds.layer_by_name(name).with_context(|| {
+                format!("Dataset {out_path:?} has no layer named {name:?} to append to.")
+            })?,
+            _ => ds
+                .layers()
+                .next()
+                .with_context(|| format!("Dataset {out_path:?} has no layers to append to."))?,
+        };
+        return write_features(&mut layer, geometries, &attributes);
+    }
+
     // If driver is not provided, attempt to infer it from the file extension.
-    let driver_name = driver.unwrap_or_else(|| {
-    let driver = GdalDrivers
-        .infer_driver_name(out_path.extension().expect(format!("Path {out_path:?} does not have a valid extension.").as_str()).to_str().unwrap())
-        .expect("Could not infer driver by file extension. Consider specifying the GDAL_DRIVER environment variable.");
-    driver.1.get("write").unwrap().clone().expect(format!("Driver {} is not writeable.", driver.0).as_str());
-    driver.0
-});
+    let driver_name = match driver {
+        Some(driver_name) => driver_name,
+        None => {
+            let path_str = out_path.to_string_lossy().into_owned();
+            compound_extension_candidates(&path_str)
+                .iter()
+                .rev()
+                .find_map(|extension| {
+                    GdalDrivers
+                        .infer_drivers(extension, true, false)
+                        .into_iter()
+                        .find(|(_, properties)| properties.get("write").unwrap().is_some())
+                })
+                .map(|(name, _)| name)
+                .with_context(|| {
+                    format!(
+                        "Could not infer a writeable driver for {out_path:?}. Consider specifying the GDAL_DRIVER environment variable."
+                    )
+                })?
+        }
+    };
     let drv = gdal::DriverManager::get_driver_by_name(&driver_name)
-        .expect(format!("Driver {driver_name} does not exist.").as_str());
+        .with_context(|| format!("Driver {driver_name} does not exist."))?;
 
-    let mut ds = drv.create_vector_only(out_path).unwrap();
+    let mut ds = drv
+        .create_vector_only(out_path)
+        .with_context(|| format!("Failed to create dataset at path {out_path:?}"))?;
     let options = options.unwrap_or(LayerOptions {
         ..Default::default()
     });
-    let mut lyr = ds.create_layer(options).unwrap();
-    geometries.into_iter().for_each(|geom| {
-        lyr.create_feature(geom).expect("Couldn't write geometry");
-    });
+    let mut layer = ds
+        .create_layer(options)
+        .with_context(|| format!("Failed to create a layer in {out_path:?}"))?;
+    // Union the field names across every feature's attributes, rather than just the first
+    // feature's, since features carried through from heterogeneous sources may not all set the
+    // same fields.
+    let mut defined_fields = std::collections::HashSet::new();
+    for fields in &attributes {
+        for field in fields {
+            if defined_fields.insert(field.name.clone()) {
+                gdal::vector::FieldDefn::new(&field.name, ogr_field_type(&field.value))
+                    .with_context(|| format!("Failed to define field {:?}.", field.name))?
+                    .add_to_layer(&layer)
+                    .with_context(|| format!("Failed to add field {:?} to the layer.", field.name))?;
+            }
+        }
+    }
+    write_features(&mut layer, geometries, &attributes)
+}
+
+/// Parses WKT text into geometries, one geometry per non-empty line, without needing a
+/// GDAL-readable file on disk. Lets a dataset be handed to a rule as plain WKT, which is handy
+/// for quick scripts and tests where writing a shapefile/GeoPackage is overkill.
+pub fn geometries_from_wkt(wkt: &str) -> anyhow::Result<Vec<geo::Geometry<f64>>> {
+    crate::from_wkt(
+        &wkt.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Writes `geometries` out as WKT text, one geometry per line — the inverse of
+/// [`geometries_from_wkt`]. Lets topology errors be copy-pasted straight into a WKT-aware
+/// viewer instead of round-tripping through a GDAL driver.
+pub fn geometries_to_wkt<T: geo::GeoFloat>(geometries: &[geo::Geometry<T>]) -> String {
+    super::wkt::to_wkt(geometries).join("\n")
 }
 
 pub fn validate_srs(dataset1: &VectorDataset, dataset2: &VectorDataset) -> anyhow::Result<()> {
-    let comparison = dataset1.compare_srs(dataset2).unwrap();
+    let comparison = dataset1.compare_srs(dataset2)?;
     match comparison {
         SRSComparison::Different(crs1, crs2) => {
             return Err(anyhow::anyhow!(
@@ -100,47 +285,157 @@ pub fn validate_srs(dataset1: &VectorDataset, dataset2: &VectorDataset) -> anyho
     }
 }
 
+/// Chooses how error geometries are written out, alongside [`GdalDrivers`]: either through a
+/// GDAL driver as today, or as plain [`geometries_to_wkt`] text when no GDAL dataset is wanted
+/// at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Gdal,
+    Wkt,
+}
+
+/// Chooses how [`harmonize_srs`] handles a CRS mismatch between two datasets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrsMode {
+    /// Fail on a mismatch, same as [`validate_srs`].
+    Strict,
+    /// Reproject the second dataset's geometries into the first dataset's CRS on a mismatch.
+    Reproject,
+}
+
+/// Like [`validate_srs`], but in [`SrsMode::Reproject`] mode reprojects `geometries2` into
+/// `dataset1`'s CRS instead of failing, so topology checks can run across layers delivered in
+/// different projections (e.g. a municipal dataset in a local grid against a national one in a
+/// geographic CRS). Returns `geometries2` unchanged when the CRS already match or either
+/// dataset is missing one.
+pub fn harmonize_srs<T: geo::GeoFloat>(
+    dataset1: &VectorDataset,
+    dataset2: &VectorDataset,
+    geometries2: Vec<geo::Geometry<T>>,
+    mode: SrsMode,
+) -> anyhow::Result<Vec<geo::Geometry<T>>> {
+    let (wkt1, wkt2) = match dataset1.compare_srs(dataset2)? {
+        SRSComparison::Same | SRSComparison::Missing => return Ok(geometries2),
+        SRSComparison::Different(wkt1, wkt2) => (wkt1, wkt2),
+    };
+    if mode == SrsMode::Strict {
+        return Err(anyhow::anyhow!(
+            "The crs of the input datasets is different. Found {} and {}",
+            wkt1,
+            wkt2
+        ));
+    }
+    let source = gdal::spatial_ref::SpatialRef::from_wkt(&wkt2)
+        .with_context(|| "Failed to parse the second dataset's spatial reference.")?;
+    let target = gdal::spatial_ref::SpatialRef::from_wkt(&wkt1)
+        .with_context(|| "Failed to parse the first dataset's spatial reference.")?;
+    let transform = gdal::spatial_ref::CoordTransform::new(&source, &target)
+        .with_context(|| "Failed to build a coordinate transform between the two datasets' CRS.")?;
+    geometries2
+        .into_iter()
+        .map(|geometry| reproject_geometry(geometry, &transform))
+        .collect()
+}
+
+fn reproject_geometry<T: geo::GeoFloat>(
+    geometry: geo::Geometry<T>,
+    transform: &gdal::spatial_ref::CoordTransform,
+) -> anyhow::Result<geo::Geometry<T>> {
+    use geo::MapCoordsNum;
+    geometry.try_map_coords(|coord| {
+        let mut xs = [coord.x.to_f64().ok_or_else(|| anyhow::anyhow!("Failed to convert a coordinate to f64 for reprojection."))?];
+        let mut ys = [coord.y.to_f64().ok_or_else(|| anyhow::anyhow!("Failed to convert a coordinate to f64 for reprojection."))?];
+        let mut zs = [0.0];
+        transform
+            .transform_coords(&mut xs, &mut ys, &mut zs)
+            .with_context(|| "Failed to reproject a coordinate.")?;
+        Ok(geo::Coord {
+            x: T::from(xs[0]).ok_or_else(|| anyhow::anyhow!("Reprojected coordinate does not fit the target precision."))?,
+            y: T::from(ys[0]).ok_or_else(|| anyhow::anyhow!("Reprojected coordinate does not fit the target precision."))?,
+        })
+    })
+}
+
 pub struct GdalDrivers;
 type DriverProps = HashMap<&'static str, Option<String>>;
 
 impl GdalDrivers {
+    /// Resolves every driver that can read/write the given file `extension`, ranked so that
+    /// a driver whose preferred `DMD_EXTENSION` equals the input comes before one that merely
+    /// lists it among several `DMD_EXTENSIONS` tokens. Both sides are lowercase-normalized and
+    /// compared as whole tokens, so `"json"` never matches a driver only advertising
+    /// `"geojson"`, and `"shp"` never matches `"shpx"`.
+    ///
+    /// `vector`/`raster` restrict the search to drivers declaring the matching `DCAP_*`
+    /// capability; pass `(true, false)` for the vector-only lookups this crate needs.
+    pub fn infer_drivers(
+        &self,
+        extension: &str,
+        vector: bool,
+        raster: bool,
+    ) -> Vec<(String, DriverProps)> {
+        let extension = extension.to_lowercase();
+        let mut candidates: Vec<(String, DriverProps, bool)> = self
+            .driver_map()
+            .into_iter()
+            .filter(|(_, properties)| {
+                (!vector || properties.get("vector").unwrap().is_some())
+                    && (!raster || properties.get("raster").unwrap().is_some())
+            })
+            .filter_map(|(name, properties)| {
+                let extensions = properties.get("extensions").unwrap().clone().unwrap_or_default();
+                let tokens: Vec<String> = extensions
+                    .split_whitespace()
+                    .map(|token| token.to_lowercase())
+                    .collect();
+                if !tokens.iter().any(|token| token == &extension) {
+                    return None;
+                }
+                let preferred = properties
+                    .get("extension")
+                    .unwrap()
+                    .clone()
+                    .map(|preferred| preferred.to_lowercase() == extension)
+                    .unwrap_or(false);
+                Some((name, properties, preferred))
+            })
+            .collect();
+        // Drivers whose singular, preferred extension matches exactly are ranked first.
+        candidates.sort_by_key(|(_, _, preferred)| !preferred);
+        candidates
+            .into_iter()
+            .map(|(name, properties, _)| (name, properties))
+            .collect()
+    }
+
+    /// Resolves the single best driver for `extension`, kept for the common case where the
+    /// caller just wants the top-ranked candidate from [`Self::infer_drivers`].
     pub fn infer_driver_name(&self, extension: &str) -> Option<(String, DriverProps)> {
-        // Finds out whether or not the input file suffix can be mapped to a valid driver.
-        self.driver_map().into_iter().find(|(_, properties)| {
-            if properties
-                .get("extensions")
-                .unwrap()
-                .clone()
-                .unwrap()
-                .contains(extension)
-            {
-                return true;
-            }
-            false
-        })
+        self.infer_drivers(extension, true, false).into_iter().next()
     }
 
     fn driver_map(&self) -> HashMap<String, DriverProps> {
         let mut drivers = HashMap::new();
         for i in 0..gdal::DriverManager::count() {
             let driver = gdal::DriverManager::get_driver(i).unwrap();
-            let mut extension = driver.metadata_item("DMD_EXTENSION", "");
-            if let Some(extensions) = driver.metadata_item("DMD_EXTENSIONS", "") {
-                // DMD_EXTENSIONS takes priority over DMD_EXTENSION
-                if !extensions.is_empty() {
-                    extension = Some(extensions)
-                }
+            let extension = driver.metadata_item("DMD_EXTENSION", "");
+            let mut extensions = driver.metadata_item("DMD_EXTENSIONS", "");
+            if extensions.as_deref().unwrap_or_default().is_empty() {
+                // Fall back to the singular, preferred extension when the driver doesn't
+                // advertise the plural, space-separated token list.
+                extensions = extension.clone();
             }
             let mut properties = HashMap::new();
             properties.insert("read", driver.metadata_item("DCAP_OPEN", ""));
             properties.insert("write", driver.metadata_item("DCAP_CREATE", ""));
-            properties.insert("extensions", extension);
+            properties.insert("vector", driver.metadata_item("DCAP_VECTOR", ""));
+            properties.insert("raster", driver.metadata_item("DCAP_RASTER", ""));
+            properties.insert("extension", extension);
+            properties.insert("extensions", extensions);
 
-            if let Some(extension) = properties.get("extensions").unwrap() {
-                if !extension.is_empty()
-                    && driver.metadata_item("DCAP_VECTOR", "").is_some()
-                    && !driver.short_name().is_empty()
-                {
+            if let Some(extensions) = properties.get("extensions").unwrap() {
+                if !extensions.is_empty() && !driver.short_name().is_empty() {
                     drivers.insert(driver.short_name(), properties);
                 }
             }
@@ -152,7 +447,8 @@ impl GdalDrivers {
         self.driver_map()
             .into_iter()
             .filter_map(|(driver, properties)| {
-                if properties.get("read").unwrap().is_some()
+                if properties.get("vector").unwrap().is_some()
+                    && properties.get("read").unwrap().is_some()
                     && properties.get("write").unwrap().is_some()
                 {
                     return Some((
@@ -169,7 +465,9 @@ impl GdalDrivers {
         self.driver_map()
             .into_iter()
             .filter_map(|(driver, properties)| {
-                if properties.get("read").unwrap().is_some() {
+                if properties.get("vector").unwrap().is_some()
+                    && properties.get("read").unwrap().is_some()
+                {
                     return Some((
                         driver,
                         properties.get("extensions").unwrap().clone().unwrap(),
@@ -184,7 +482,9 @@ impl GdalDrivers {
         self.driver_map()
             .into_iter()
             .filter_map(|(driver, properties)| {
-                if properties.get("write").unwrap().is_some() {
+                if properties.get("vector").unwrap().is_some()
+                    && properties.get("write").unwrap().is_some()
+                {
                     return Some((
                         driver,
                         properties.get("extensions").unwrap().clone().unwrap(),
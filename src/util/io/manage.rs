@@ -0,0 +1,59 @@
+//! Dataset lifecycle helpers built on top of the GDAL driver itself, rather than raw
+//! filesystem calls, so sidecar files (`.shp`/`.shx`/`.dbf`/`.prj`, GeoPackage `-wal`/`-shm`
+//! files, etc.) are handled the same way the owning driver expects.
+use super::GdalDrivers;
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+fn driver_for_path(path: &Path) -> anyhow::Result<gdal::Driver> {
+    let extension = super::compound_extension_candidates(&path.to_string_lossy())
+        .into_iter()
+        .rev()
+        .find(|candidate| GdalDrivers.infer_driver_name(candidate).is_some())
+        .with_context(|| format!("Could not infer a driver for {path:?}."))?;
+    let driver_name = GdalDrivers
+        .infer_driver_name(&extension)
+        .with_context(|| format!("Could not infer a driver for {path:?}."))?
+        .0;
+    gdal::DriverManager::get_driver_by_name(&driver_name)
+        .with_context(|| format!("Driver {driver_name} does not exist."))
+}
+
+/// Copies a dataset at `src` to `dst` using the driver's own `CreateCopy`, so sidecar files
+/// (shapefile `.shx`/`.dbf`/`.prj`, etc.) are produced alongside the main file just like the
+/// driver would do on a fresh write. `driver` overrides the one inferred from `dst`'s
+/// extension, reusing the same inference [`crate::util::create_dataset`] relies on.
+pub fn copy_dataset(src: &Path, dst: &Path, driver: Option<String>) -> anyhow::Result<gdal::Dataset> {
+    let source = gdal::Dataset::open(src).with_context(|| format!("Failed to open {src:?}."))?;
+    let drv = match driver {
+        Some(driver_name) => gdal::DriverManager::get_driver_by_name(&driver_name)
+            .with_context(|| format!("Driver {driver_name} does not exist."))?,
+        None => driver_for_path(dst)?,
+    };
+    drv.create_copy(&source, dst)
+        .with_context(|| format!("Failed to copy {src:?} to {dst:?}."))
+}
+
+/// Deletes every file belonging to the dataset at `path` (e.g. a shapefile's `.shp`, `.shx`,
+/// `.dbf`, `.prj` siblings), via the owning driver's `Delete` rather than a single
+/// `fs::remove_file`, which would silently orphan the sidecars.
+pub fn delete_dataset(path: &Path) -> anyhow::Result<()> {
+    let drv = driver_for_path(path)?;
+    drv.delete(path)
+        .with_context(|| format!("Failed to delete the dataset at {path:?}."))
+}
+
+/// Renames every file belonging to the dataset at `from` to `to`, via the owning driver's
+/// `Rename` so sidecar files move together rather than leaving stragglers behind.
+pub fn rename_dataset(from: &Path, to: &Path) -> anyhow::Result<()> {
+    let drv = driver_for_path(from)?;
+    drv.rename(to, from)
+        .with_context(|| format!("Failed to rename the dataset at {from:?} to {to:?}."))
+}
+
+/// Returns the path to every file belonging to the dataset at `path` (e.g. a shapefile's
+/// sidecars), as reported by the driver's `CopyFiles`/file-list machinery.
+pub fn dataset_files(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let dataset = gdal::Dataset::open(path).with_context(|| format!("Failed to open {path:?}."))?;
+    Ok(dataset.file_list().into_iter().map(PathBuf::from).collect())
+}
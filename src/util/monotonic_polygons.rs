@@ -0,0 +1,643 @@
+use crate::util::PartitionedPolygons;
+use geo::{Coord, CoordPos, GeoFloat, LineString, Point, Polygon};
+use rayon::prelude::*;
+
+/// Signed turn direction of `a -> b -> c`: positive for a left turn, negative for a right turn,
+/// zero when the three points are collinear.
+fn orientation<T: GeoFloat>(a: Coord<T>, b: Coord<T>, c: Coord<T>) -> T {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Lexicographic sweep order used throughout this module: smaller `x` first, ties broken by
+/// smaller `y`. This is the order in which the plane sweep visits vertices.
+fn sweep_before<T: GeoFloat>(a: Coord<T>, b: Coord<T>) -> bool {
+    (a.x, a.y.to_f64().unwrap_or(0.)) < (b.x, b.y.to_f64().unwrap_or(0.))
+        || (a.x == b.x && a.y < b.y)
+}
+
+/// The five vertex categories the plane sweep in [`decompose_ring`] assigns while scanning a
+/// polygon ring left to right, following de Berg et al.'s monotone decomposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VertexKind {
+    Start,
+    End,
+    Split,
+    Merge,
+    Regular,
+}
+
+/// Classifies `v` (with ring neighbours `prev` and `next`) by whether its neighbours lie before
+/// or after it in sweep order and whether the ring turns left or right at `v`.
+fn classify_vertex<T: GeoFloat>(prev: Coord<T>, v: Coord<T>, next: Coord<T>) -> VertexKind {
+    let prev_before = sweep_before(prev, v);
+    let next_before = sweep_before(next, v);
+    let turn = orientation(prev, v, next);
+    if !prev_before && !next_before {
+        if turn > T::zero() {
+            VertexKind::Start
+        } else {
+            VertexKind::Split
+        }
+    } else if prev_before && next_before {
+        if turn > T::zero() {
+            VertexKind::End
+        } else {
+            VertexKind::Merge
+        }
+    } else {
+        VertexKind::Regular
+    }
+}
+
+/// One edge of the ring currently crossed by the sweep line, tracked so a split or merge vertex
+/// can find "the edge directly above it" and the diagonal-pending vertex (the `helper`) on that
+/// edge.
+struct StatusEdge<T: GeoFloat> {
+    from: Coord<T>,
+    to: Coord<T>,
+    from_index: usize,
+    to_index: usize,
+    helper: usize,
+}
+
+impl<T: GeoFloat> StatusEdge<T> {
+    /// The sweep line's y position where it crosses this edge at `x`.
+    fn y_at(&self, x: T) -> T {
+        if self.to.x == self.from.x {
+            self.from.y
+        } else {
+            let t = (x - self.from.x) / (self.to.x - self.from.x);
+            self.from.y + t * (self.to.y - self.from.y)
+        }
+    }
+}
+
+/// Finds the status edge passing directly above `v`, i.e. the edge with the smallest `y` at
+/// `v.x` that still lies above `v`. A linear scan suffices here: the `O(log n)` guarantee this
+/// module makes is for [`MonotonicPolygons::contains_points`] queries, not for index construction.
+fn edge_above<T: GeoFloat>(status: &[StatusEdge<T>], v: Coord<T>) -> Option<usize> {
+    status
+        .iter()
+        .enumerate()
+        .filter(|(_, edge)| edge.y_at(v.x) >= v.y)
+        .min_by(|(_, a), (_, b)| a.y_at(v.x).partial_cmp(&b.y_at(v.x)).unwrap())
+        .map(|(index, _)| index)
+}
+
+/// Decomposes a single ring into x-monotone pieces by a left-to-right plane sweep, returning the
+/// diagonals (pairs of vertex indices into `ring`) that split it. `ring` must already have its
+/// closing duplicate vertex stripped and be wound counter-clockwise.
+fn decompose_ring<T: GeoFloat>(ring: &[Coord<T>]) -> Vec<(usize, usize)> {
+    let n = ring.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        if sweep_before(ring[a], ring[b]) {
+            std::cmp::Ordering::Less
+        } else if ring[a] == ring[b] {
+            std::cmp::Ordering::Equal
+        } else {
+            std::cmp::Ordering::Greater
+        }
+    });
+
+    let mut status: Vec<StatusEdge<T>> = Vec::new();
+    let mut diagonals: Vec<(usize, usize)> = Vec::new();
+    let next_index = |i: usize| (i + 1) % n;
+    let prev_index = |i: usize| (i + n - 1) % n;
+
+    for i in order {
+        let prev = ring[prev_index(i)];
+        let v = ring[i];
+        let next = ring[next_index(i)];
+        match classify_vertex(prev, v, next) {
+            VertexKind::Start => {
+                status.push(StatusEdge {
+                    from: v,
+                    to: next,
+                    from_index: i,
+                    to_index: next_index(i),
+                    helper: i,
+                });
+            }
+            VertexKind::Split => {
+                if let Some(above) = edge_above(&status, v) {
+                    diagonals.push((i, status[above].helper));
+                    status[above].helper = i;
+                }
+                status.push(StatusEdge {
+                    from: v,
+                    to: next,
+                    from_index: i,
+                    to_index: next_index(i),
+                    helper: i,
+                });
+            }
+            VertexKind::End => {
+                if let Some(pos) = status.iter().position(|edge| edge.to_index == i) {
+                    if matches!(ring_helper_kind(&status[pos], ring, n), VertexKind::Merge) {
+                        diagonals.push((i, status[pos].helper));
+                    }
+                    status.remove(pos);
+                }
+            }
+            VertexKind::Merge => {
+                if let Some(pos) = status.iter().position(|edge| edge.to_index == i) {
+                    if matches!(ring_helper_kind(&status[pos], ring, n), VertexKind::Merge) {
+                        diagonals.push((i, status[pos].helper));
+                    }
+                    status.remove(pos);
+                }
+                if let Some(above) = edge_above(&status, v) {
+                    if matches!(ring_helper_kind(&status[above], ring, n), VertexKind::Merge) {
+                        diagonals.push((i, status[above].helper));
+                    }
+                    status[above].helper = i;
+                }
+            }
+            VertexKind::Regular => {
+                if let Some(pos) = status.iter().position(|edge| edge.to_index == i) {
+                    if matches!(ring_helper_kind(&status[pos], ring, n), VertexKind::Merge) {
+                        diagonals.push((i, status[pos].helper));
+                    }
+                    status.remove(pos);
+                    let target = if sweep_before(next, prev) { prev_index(i) } else { next_index(i) };
+                    status.push(StatusEdge {
+                        from: v,
+                        to: ring[target],
+                        from_index: i,
+                        to_index: target,
+                        helper: i,
+                    });
+                } else if let Some(above) = edge_above(&status, v) {
+                    if matches!(ring_helper_kind(&status[above], ring, n), VertexKind::Merge) {
+                        diagonals.push((i, status[above].helper));
+                    }
+                    status[above].helper = i;
+                }
+            }
+        }
+    }
+    diagonals
+}
+
+/// Re-derives the [`VertexKind`] of `edge`'s current helper, so the sweep can tell whether a
+/// pending helper still needs a diagonal before it is overwritten or its edge removed.
+fn ring_helper_kind<T: GeoFloat>(edge: &StatusEdge<T>, ring: &[Coord<T>], n: usize) -> VertexKind {
+    let helper = edge.helper;
+    let next_index = (helper + 1) % n;
+    let prev_index = (helper + n - 1) % n;
+    classify_vertex(ring[prev_index], ring[helper], ring[next_index])
+}
+
+/// A single x-monotone piece of a decomposed ring: a `lower` chain and an `upper` chain, both
+/// sorted left to right by `x`, meeting at the piece's leftmost and rightmost vertices.
+struct MonotonePiece<T: GeoFloat> {
+    lower: Vec<Coord<T>>,
+    upper: Vec<Coord<T>>,
+}
+
+impl<T: GeoFloat> MonotonePiece<T> {
+    fn x_range(&self) -> (T, T) {
+        (self.lower[0].x, self.lower[self.lower.len() - 1].x)
+    }
+
+    /// Tests whether `point` lies inside, on the boundary of, or below/above this piece, or
+    /// returns `None` when `point.x` falls outside the piece entirely.
+    fn contains(&self, point: Coord<T>) -> Option<CoordPos> {
+        let (min_x, max_x) = self.x_range();
+        if point.x < min_x || point.x > max_x {
+            return None;
+        }
+        // A vertical chain edge at `point.x` has no single interpolated `y`, so test it directly.
+        for chain in [&self.lower, &self.upper] {
+            for edge in chain.windows(2) {
+                let (a, b) = (edge[0], edge[1]);
+                if a.x == point.x && b.x == point.x && point.y >= a.y.min(b.y) && point.y <= a.y.max(b.y) {
+                    return Some(CoordPos::OnBoundary);
+                }
+            }
+        }
+        let lower_y = interpolate_chain(&self.lower, point.x)?;
+        let upper_y = interpolate_chain(&self.upper, point.x)?;
+        if point.y == lower_y || point.y == upper_y {
+            Some(CoordPos::OnBoundary)
+        } else if point.y > lower_y && point.y < upper_y {
+            Some(CoordPos::Inside)
+        } else {
+            Some(CoordPos::Outside)
+        }
+    }
+}
+
+/// Binary-searches `chain` (sorted by `x`) for the segment spanning `x` and linearly interpolates
+/// its `y`. Returns `None` if `x` falls outside the chain.
+fn interpolate_chain<T: GeoFloat>(chain: &[Coord<T>], x: T) -> Option<T> {
+    if x < chain[0].x || x > chain[chain.len() - 1].x {
+        return None;
+    }
+    let idx = chain.partition_point(|c| c.x <= x);
+    if idx == 0 {
+        return Some(chain[0].y);
+    }
+    if idx >= chain.len() {
+        return Some(chain[chain.len() - 1].y);
+    }
+    let (a, b) = (chain[idx - 1], chain[idx]);
+    if a.x == b.x {
+        return Some(a.y.max(b.y));
+    }
+    let t = (x - a.x) / (b.x - a.x);
+    Some(a.y + t * (b.y - a.y))
+}
+
+/// Signed area of the loop `vertices` (indices into `ring`), positive for counter-clockwise.
+fn loop_area_sign<T: GeoFloat>(ring: &[Coord<T>], vertices: &[usize]) -> T {
+    let mut area = T::zero();
+    for i in 0..vertices.len() {
+        let a = ring[vertices[i]];
+        let b = ring[vertices[(i + 1) % vertices.len()]];
+        area = area + (a.x * b.y - b.x * a.y);
+    }
+    area
+}
+
+/// Traces the faces bounded by `ring`'s edges plus its `diagonals`, keeping only the
+/// counter-clockwise (interior) ones, and splits each into a [`MonotonePiece`].
+fn build_monotone_pieces<T: GeoFloat>(ring: &[Coord<T>], diagonals: &[(usize, usize)]) -> Vec<MonotonePiece<T>> {
+    let n = ring.len();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut add_edge = |a: usize, b: usize, adjacency: &mut Vec<Vec<usize>>| {
+        if !adjacency[a].contains(&b) {
+            adjacency[a].push(b);
+        }
+    };
+    for i in 0..n {
+        add_edge(i, (i + 1) % n, &mut adjacency);
+        add_edge((i + 1) % n, i, &mut adjacency);
+    }
+    for &(a, b) in diagonals {
+        add_edge(a, b, &mut adjacency);
+        add_edge(b, a, &mut adjacency);
+    }
+
+    let angle = |from: usize, to: usize| -> T {
+        let a = ring[from];
+        let b = ring[to];
+        (b.y - a.y).atan2(b.x - a.x)
+    };
+    let two_pi = T::from(std::f64::consts::PI * 2.).unwrap();
+    let normalize = |mut a: T| {
+        while a < T::zero() {
+            a = a + two_pi;
+        }
+        while a >= two_pi {
+            a = a - two_pi;
+        }
+        a
+    };
+
+    let mut visited: std::collections::HashSet<(usize, usize)> = std::collections::HashSet::new();
+    let mut pieces = Vec::new();
+    for start_from in 0..n {
+        for &start_to in &adjacency[start_from].clone() {
+            if visited.contains(&(start_from, start_to)) {
+                continue;
+            }
+            let mut loop_vertices = vec![start_from];
+            let (mut from, mut to) = (start_from, start_to);
+            loop {
+                visited.insert((from, to));
+                loop_vertices.push(to);
+                let incoming_angle = normalize(angle(to, from) + two_pi / T::from(2.).unwrap());
+                let candidates = &adjacency[to];
+                let next = candidates
+                    .iter()
+                    .filter(|&&candidate| candidate != from || candidates.len() == 1)
+                    .min_by(|&&a, &&b| {
+                        let da = normalize(angle(to, a) - incoming_angle);
+                        let db = normalize(angle(to, b) - incoming_angle);
+                        da.partial_cmp(&db).unwrap()
+                    })
+                    .copied();
+                match next {
+                    Some(next) => {
+                        from = to;
+                        to = next;
+                        if from == start_from && to == start_to {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            loop_vertices.pop();
+            if loop_vertices.len() >= 3 && loop_area_sign(ring, &loop_vertices) > T::zero() {
+                if let Some(piece) = piece_from_loop(ring, &loop_vertices) {
+                    pieces.push(piece);
+                }
+            }
+        }
+    }
+    pieces
+}
+
+/// Splits a counter-clockwise `loop_vertices` (indices into `ring`) into its lower chain (the
+/// forward walk from the leftmost to the rightmost vertex) and upper chain (the remaining walk,
+/// reversed so it too reads left to right).
+fn piece_from_loop<T: GeoFloat>(ring: &[Coord<T>], loop_vertices: &[usize]) -> Option<MonotonePiece<T>> {
+    let len = loop_vertices.len();
+    let (left_pos, _) = loop_vertices
+        .iter()
+        .enumerate()
+        .min_by(|(_, &a), (_, &b)| if sweep_before(ring[a], ring[b]) { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater })?;
+    let (right_pos, _) = loop_vertices
+        .iter()
+        .enumerate()
+        .max_by(|(_, &a), (_, &b)| if sweep_before(ring[a], ring[b]) { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater })?;
+
+    let mut lower = Vec::new();
+    let mut i = left_pos;
+    loop {
+        lower.push(ring[loop_vertices[i]]);
+        if i == right_pos {
+            break;
+        }
+        i = (i + 1) % len;
+    }
+    let mut upper = Vec::new();
+    let mut i = right_pos;
+    loop {
+        upper.push(ring[loop_vertices[i]]);
+        if i == left_pos {
+            break;
+        }
+        i = (i + 1) % len;
+    }
+    upper.reverse();
+    Some(MonotonePiece { lower, upper })
+}
+
+/// Strips a ring's closing duplicate vertex and reorders it to counter-clockwise winding, the
+/// orientation [`decompose_ring`] assumes.
+fn canonical_ccw_ring<T: GeoFloat>(ring: &LineString<T>) -> Vec<Coord<T>> {
+    let coords = &ring.0[..ring.0.len().saturating_sub(1)]; // drop the closing duplicate vertex
+    let indices: Vec<usize> = (0..coords.len()).collect();
+    if loop_area_sign(coords, &indices) < T::zero() {
+        coords.iter().rev().copied().collect()
+    } else {
+        coords.to_vec()
+    }
+}
+
+/// Decomposes `polygon` into monotone pieces, appending its exterior's pieces to `exterior_pieces`
+/// and every interior ring's pieces (decomposed the same way as any other ring) to `hole_pieces`.
+fn decompose_polygon<T: GeoFloat>(
+    polygon: &Polygon<T>,
+    exterior_pieces: &mut Vec<MonotonePiece<T>>,
+    hole_pieces: &mut Vec<MonotonePiece<T>>,
+) {
+    let exterior = canonical_ccw_ring(polygon.exterior());
+    let diagonals = decompose_ring(&exterior);
+    exterior_pieces.extend(build_monotone_pieces(&exterior, &diagonals));
+    for interior in polygon.interiors() {
+        let ring = canonical_ccw_ring(interior);
+        let diagonals = decompose_ring(&ring);
+        hole_pieces.extend(build_monotone_pieces(&ring, &diagonals));
+    }
+}
+
+/// A node of a static, centered interval tree over a slice of [`MonotonePiece`] `x`-ranges,
+/// following Cormen et al.'s "interval tree" layout: `center` splits the remaining pieces into
+/// those entirely to its left, entirely to its right, and those straddling it, with the
+/// straddling set indexed twice (once sorted by `min_x`, once by `max_x`) so a query can stop
+/// scanning as soon as it walks past the pieces that could still overlap the queried `x`.
+struct IntervalNode<T: GeoFloat> {
+    center: T,
+    by_min_x: Vec<usize>,
+    by_max_x: Vec<usize>,
+    left: Option<Box<IntervalNode<T>>>,
+    right: Option<Box<IntervalNode<T>>>,
+}
+
+/// Builds an [`IntervalNode`] tree over `indices` (positions into `pieces`), or `None` for an
+/// empty slice.
+fn build_interval_tree<T: GeoFloat>(indices: &[usize], pieces: &[MonotonePiece<T>]) -> Option<Box<IntervalNode<T>>> {
+    if indices.is_empty() {
+        return None;
+    }
+    let mut endpoints: Vec<T> = indices
+        .iter()
+        .flat_map(|&i| {
+            let (min_x, max_x) = pieces[i].x_range();
+            [min_x, max_x]
+        })
+        .collect();
+    endpoints.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let center = endpoints[endpoints.len() / 2];
+
+    let (mut left, mut right, mut straddling) = (Vec::new(), Vec::new(), Vec::new());
+    for &i in indices {
+        let (min_x, max_x) = pieces[i].x_range();
+        if max_x < center {
+            left.push(i);
+        } else if min_x > center {
+            right.push(i);
+        } else {
+            straddling.push(i);
+        }
+    }
+
+    let mut by_min_x = straddling.clone();
+    by_min_x.sort_by(|&a, &b| pieces[a].x_range().0.partial_cmp(&pieces[b].x_range().0).unwrap());
+    let mut by_max_x = straddling;
+    by_max_x.sort_by(|&a, &b| pieces[b].x_range().1.partial_cmp(&pieces[a].x_range().1).unwrap());
+
+    Some(Box::new(IntervalNode {
+        center,
+        by_min_x,
+        by_max_x,
+        left: build_interval_tree(&left, pieces),
+        right: build_interval_tree(&right, pieces),
+    }))
+}
+
+/// Collects (into `out`) the indices of every piece in `pieces` whose `x`-range contains `x`, by
+/// walking `node` top to bottom: `O(log n)` to descend to the relevant pieces, plus `O(k)` to
+/// report the `k` pieces actually straddling `x`.
+fn query_interval_tree<T: GeoFloat>(node: &Option<Box<IntervalNode<T>>>, x: T, pieces: &[MonotonePiece<T>], out: &mut Vec<usize>) {
+    let Some(node) = node else { return };
+    match x.partial_cmp(&node.center).unwrap() {
+        std::cmp::Ordering::Less => {
+            for &i in &node.by_min_x {
+                if pieces[i].x_range().0 > x {
+                    break;
+                }
+                out.push(i);
+            }
+            query_interval_tree(&node.left, x, pieces, out);
+        }
+        std::cmp::Ordering::Greater => {
+            for &i in &node.by_max_x {
+                if pieces[i].x_range().1 < x {
+                    break;
+                }
+                out.push(i);
+            }
+            query_interval_tree(&node.right, x, pieces, out);
+        }
+        std::cmp::Ordering::Equal => out.extend(node.by_min_x.iter().copied()),
+    }
+}
+
+/// A monotone-subdivision spatial index for fast point-in-polygon testing, built once from a set
+/// of polygons and then queried many times in `O(log n)` per point instead of re-testing every
+/// point against every polygon's full ring.
+///
+/// Each polygon is decomposed into x-monotone pieces by a left-to-right plane sweep (see
+/// [`decompose_ring`]); interior rings (holes) are decomposed the same way and subtracted at
+/// query time rather than bridged into the exterior, which keeps the decomposition independent
+/// per ring at the cost of one extra piece lookup per hole. An [`IntervalNode`] tree over each
+/// piece set's `x`-range narrows a query down to the handful of candidate pieces spanning the
+/// queried point before [`MonotonePiece::contains`] does the chain lookup, rather than scanning
+/// every piece in the index.
+pub struct MonotonicPolygons<T: GeoFloat + Send + Sync> {
+    exterior_pieces: Vec<MonotonePiece<T>>,
+    hole_pieces: Vec<MonotonePiece<T>>,
+    exterior_index: Option<Box<IntervalNode<T>>>,
+    hole_index: Option<Box<IntervalNode<T>>>,
+}
+
+impl<T: GeoFloat + Send + Sync> MonotonicPolygons<T> {
+    /// Builds the index from `polygons`, decomposing every polygon (including those flattened out
+    /// of multipolygons) in turn.
+    pub fn build(polygons: PartitionedPolygons<T>) -> Self {
+        let mut exterior_pieces = Vec::new();
+        let mut hole_pieces = Vec::new();
+        for polygon in polygons.into_iter() {
+            decompose_polygon(&polygon, &mut exterior_pieces, &mut hole_pieces);
+        }
+        let exterior_index = build_interval_tree(&(0..exterior_pieces.len()).collect::<Vec<_>>(), &exterior_pieces);
+        let hole_index = build_interval_tree(&(0..hole_pieces.len()).collect::<Vec<_>>(), &hole_pieces);
+        MonotonicPolygons {
+            exterior_pieces,
+            hole_pieces,
+            exterior_index,
+            hole_index,
+        }
+    }
+
+    /// Tests a single point against the index: `OnBoundary` wins over `Inside`/`Outside`, a point
+    /// inside some hole is `Outside`, and a point inside an exterior piece and no hole is `Inside`.
+    fn contains_point(&self, point: Coord<T>) -> CoordPos {
+        let mut candidates = Vec::new();
+        query_interval_tree(&self.exterior_index, point.x, &self.exterior_pieces, &mut candidates);
+        let mut in_exterior = false;
+        for &index in &candidates {
+            match self.exterior_pieces[index].contains(point) {
+                Some(CoordPos::OnBoundary) => return CoordPos::OnBoundary,
+                Some(CoordPos::Inside) => in_exterior = true,
+                _ => {}
+            }
+        }
+
+        candidates.clear();
+        query_interval_tree(&self.hole_index, point.x, &self.hole_pieces, &mut candidates);
+        for &index in &candidates {
+            match self.hole_pieces[index].contains(point) {
+                Some(CoordPos::OnBoundary) => return CoordPos::OnBoundary,
+                Some(CoordPos::Inside) => return CoordPos::Outside,
+                _ => {}
+            }
+        }
+
+        if in_exterior {
+            CoordPos::Inside
+        } else {
+            CoordPos::Outside
+        }
+    }
+
+    /// Batch point-in-polygon query, parallelized across `points` with rayon.
+    pub fn contains_points(&self, points: &[Point<T>]) -> Vec<CoordPos>
+    where
+        T: Sync,
+    {
+        points
+            .par_iter()
+            .map(|point| self.contains_point(point.0))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::polygon;
+
+    fn index_of(polygon: Polygon<f64>) -> MonotonicPolygons<f64> {
+        MonotonicPolygons::build(PartitionedPolygons(vec![polygon], Vec::new()))
+    }
+
+    #[test]
+    fn classifies_points_inside_outside_and_on_boundary_of_a_square() {
+        let square = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 4.0, y: 0.0),
+            (x: 4.0, y: 4.0),
+            (x: 0.0, y: 4.0),
+        ];
+        let index = index_of(square);
+        let points = [
+            Point::new(2.0, 2.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 2.0),
+        ];
+        assert_eq!(
+            index.contains_points(&points),
+            vec![CoordPos::Inside, CoordPos::Outside, CoordPos::OnBoundary]
+        );
+    }
+
+    #[test]
+    fn excludes_points_inside_a_hole() {
+        let square_with_hole = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+            vec![LineString::from(vec![
+                (3.0, 3.0),
+                (7.0, 3.0),
+                (7.0, 7.0),
+                (3.0, 7.0),
+            ])],
+        );
+        let index = index_of(square_with_hole);
+        let points = [Point::new(1.0, 1.0), Point::new(5.0, 5.0)];
+        assert_eq!(
+            index.contains_points(&points),
+            vec![CoordPos::Inside, CoordPos::Outside]
+        );
+    }
+
+    #[test]
+    fn decomposes_an_l_shaped_ring_into_monotone_pieces() {
+        // A non-convex ring: the reflex corner at (2, 2) has both neighbours after it in sweep
+        // order, so the sweep classifies it as a split vertex.
+        let l_shape = polygon![
+            (x: 0.0, y: 0.0),
+            (x: 4.0, y: 0.0),
+            (x: 4.0, y: 2.0),
+            (x: 2.0, y: 2.0),
+            (x: 2.0, y: 4.0),
+            (x: 0.0, y: 4.0),
+        ];
+        let index = index_of(l_shape);
+        let points = [
+            Point::new(1.0, 1.0),
+            Point::new(3.0, 1.0),
+            Point::new(3.0, 3.0),
+        ];
+        assert_eq!(
+            index.contains_points(&points),
+            vec![CoordPos::Inside, CoordPos::Inside, CoordPos::Outside]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::util::{
+    explode_geometries, intersections, polygon_interior_point, MonotonicPolygons,
+    PartitionedPolygons,
+};
+use geo::{sweep::SweepPoint, BooleanOps, BoundingRect, CoordPos, GeoFloat, Geometry, MultiPolygon, Polygon};
+use itertools::Itertools;
+
+/// A confirmed overlap between two polygons of the same [`PartitionedPolygons`] set: `indices`
+/// are the positions of the offending pair in the order [`PartitionedPolygons::into_iter`]
+/// yields them, and `region` is the overlapping area (empty when the two polygons only share a
+/// collinear boundary stretch rather than any interior area).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolygonOverlap<T: GeoFloat> {
+    pub indices: (usize, usize),
+    pub region: MultiPolygon<T>,
+}
+
+/// Validates the OGC rule that a `MultiPolygon`'s constituent polygons must not have overlapping
+/// interiors and may only touch at finitely many boundary points.
+///
+/// Candidate pairs whose bounding boxes don't even touch are skipped outright. For the rest, the
+/// existing [`intersections`] sweep runs over the pair's exploded boundary segments: a non-empty
+/// collinear result means the boundaries run on top of each other for a stretch rather than
+/// meeting at isolated points, which is itself a violation. Otherwise a representative interior
+/// point of each polygon is tested against the other with [`MonotonicPolygons`] (reusing its
+/// `CoordPos` containment primitive) — landing `Inside` in either direction means a real interior
+/// overlap (this also catches one polygon being entirely nested inside the other, where only the
+/// smaller polygon's point would ever land inside the larger one), while `OnBoundary`/`Outside`
+/// both ways means the pair only touches at the isolated `SweepPoint`s the sweep found, which is
+/// legal and isn't reported.
+pub fn polygon_overlaps<T: GeoFloat + Send + Sync>(
+    polygons: PartitionedPolygons<T>,
+) -> Vec<PolygonOverlap<T>> {
+    let polygons: Vec<Polygon<T>> = polygons.into_iter().collect();
+    let indices: Vec<MonotonicPolygons<T>> = polygons
+        .iter()
+        .map(|polygon| MonotonicPolygons::build(PartitionedPolygons(vec![polygon.clone()], Vec::new())))
+        .collect();
+    polygons
+        .iter()
+        .zip(&indices)
+        .enumerate()
+        .tuple_combinations()
+        .filter_map(|((i, (a, a_index)), (j, (b, b_index)))| {
+            classify_pair(i, a, a_index, j, b, b_index)
+        })
+        .collect()
+}
+
+/// Classifies a single candidate pair, returning `Some` only when the pair is a real overlap.
+/// `a_index`/`b_index` are `a`/`b`'s pre-built [`MonotonicPolygons`] indices, shared across every
+/// pair each polygon participates in rather than rebuilt per pair.
+fn classify_pair<T: GeoFloat + Send + Sync>(
+    i: usize,
+    a: &Polygon<T>,
+    a_index: &MonotonicPolygons<T>,
+    j: usize,
+    b: &Polygon<T>,
+    b_index: &MonotonicPolygons<T>,
+) -> Option<PolygonOverlap<T>> {
+    let (rect_a, rect_b) = (a.bounding_rect(), b.bounding_rect());
+    if rect_a.max().x < rect_b.min().x
+        || rect_b.max().x < rect_a.min().x
+        || rect_a.max().y < rect_b.min().y
+        || rect_b.max().y < rect_a.min().y
+    {
+        return None;
+    }
+
+    let boundary_segments =
+        explode_geometries(&[Geometry::Polygon(a.clone()), Geometry::Polygon(b.clone())]);
+    let (collinear, _) = intersections::<T, SweepPoint<T>, SweepPoint<T>>(boundary_segments);
+
+    let a_in_b = b_index
+        .contains_points(&[polygon_interior_point(a)])
+        .into_iter()
+        .next()
+        .unwrap_or(CoordPos::Outside);
+    let b_in_a = a_index
+        .contains_points(&[polygon_interior_point(b)])
+        .into_iter()
+        .next()
+        .unwrap_or(CoordPos::Outside);
+
+    if collinear.is_empty() && a_in_b != CoordPos::Inside && b_in_a != CoordPos::Inside {
+        return None;
+    }
+
+    Some(PolygonOverlap {
+        indices: (i, j),
+        region: a.intersection(b),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::polygon;
+
+    #[test]
+    fn disjoint_polygons_are_valid() {
+        let a = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)];
+        let b = polygon![(x: 2., y: 2.), (x: 3., y: 2.), (x: 3., y: 3.), (x: 2., y: 3.), (x: 2., y: 2.)];
+        let overlaps = polygon_overlaps(PartitionedPolygons(vec![a, b], Vec::new()));
+        assert!(overlaps.is_empty());
+    }
+
+    #[test]
+    fn polygons_sharing_a_boundary_edge_are_an_overlap() {
+        // The squares share the whole edge from (1, 0) to (1, 1), not just isolated points.
+        let a = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)];
+        let b = polygon![(x: 1., y: 0.), (x: 2., y: 0.), (x: 2., y: 1.), (x: 1., y: 1.), (x: 1., y: 0.)];
+        let overlaps = polygon_overlaps(PartitionedPolygons(vec![a, b], Vec::new()));
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].indices, (0, 1));
+    }
+
+    #[test]
+    fn a_polygon_nested_inside_another_without_a_shared_boundary_is_an_overlap() {
+        // `b` sits entirely inside `a` with no touching edges. `a`'s own representative point
+        // never lands inside the much smaller `b`, so only the reverse direction (`b`'s point
+        // inside `a`) catches this.
+        let a = polygon![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.), (x: 0., y: 0.)];
+        let b = polygon![(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 2.), (x: 1., y: 1.)];
+        let overlaps = polygon_overlaps(PartitionedPolygons(vec![a, b], Vec::new()));
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].indices, (0, 1));
+    }
+}
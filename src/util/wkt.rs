@@ -0,0 +1,86 @@
+//! Generic-precision WKT (Well-Known Text) import/export, so a topology check can be driven by a
+//! plain-text geometry column (CSV, a wire payload) or a quick script, not only a GDAL-opened
+//! dataset, at whatever [`GeoFloat`] precision the caller needs.
+use crate::{cast_geometry, CastError};
+use geo::{GeoFloat, Geometry};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Raised by [`from_wkt_generic`] when a WKT string fails to parse or a parsed coordinate doesn't fit the
+/// target [`GeoFloat`] precision.
+#[derive(Debug)]
+pub enum WktParseError {
+    /// The text wasn't valid WKT; `text` is the offending input and `message` is the underlying
+    /// parser error.
+    Syntax { text: String, message: String },
+    /// The text parsed, but a coordinate didn't fit the requested precision.
+    Cast(CastError),
+}
+
+impl Display for WktParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WktParseError::Syntax { text, message } => {
+                write!(f, "Failed to parse WKT {text:?}: {message}")
+            }
+            WktParseError::Cast(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for WktParseError {}
+
+impl From<CastError> for WktParseError {
+    fn from(error: CastError) -> Self {
+        WktParseError::Cast(error)
+    }
+}
+
+/// Parses a batch of WKT strings into geometries at an arbitrary [`GeoFloat`] precision. Like
+/// [`crate::from_wkt`], but generic: each coordinate is converted with [`cast_geometry`]'s
+/// `T::from(..)`, so the same reader produces `Geometry<f32>` or `Geometry<f64>` on demand, and a
+/// malformed string or an out-of-range coordinate is reported through [`WktParseError`] instead of
+/// aborting the whole batch.
+pub fn from_wkt_generic<T: GeoFloat>(wkt: &[&str]) -> Result<Vec<Geometry<T>>, WktParseError> {
+    wkt.iter()
+        .map(|text| {
+            let parsed = wkt::Wkt::from_str(text).map_err(|error| WktParseError::Syntax {
+                text: text.to_string(),
+                message: error.to_string(),
+            })?;
+            Ok(cast_geometry(Geometry::<f64>::from(parsed))?)
+        })
+        .collect()
+}
+
+/// Serializes `geometries` to WKT, one string per geometry — the inverse of [`from_wkt_generic`]. Backs
+/// [`crate::util::geometries_to_wkt`]'s newline-joined output as well, so the two stay consistent.
+pub fn to_wkt<T: GeoFloat>(geometries: &[Geometry<T>]) -> Vec<String> {
+    use wkt::ToWkt;
+    geometries
+        .iter()
+        .map(|geometry| geometry.wkt_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::Geometry;
+
+    #[test]
+    fn round_trips_a_point_at_f32_precision() {
+        let geometries: Vec<Geometry<f32>> = from_wkt_generic(&["POINT(1 2)"]).unwrap();
+        assert_eq!(
+            geometries,
+            vec![Geometry::from(geo::point! { x: 1.0_f32, y: 2.0_f32 })]
+        );
+        assert_eq!(to_wkt(&geometries), vec!["POINT(1 2)"]);
+    }
+
+    #[test]
+    fn reports_malformed_wkt_instead_of_panicking() {
+        let error = from_wkt_generic::<f64>(&["NOT WKT"]).unwrap_err();
+        assert!(matches!(error, WktParseError::Syntax { .. }));
+    }
+}